@@ -0,0 +1,25 @@
+-- Durable queue of scans waiting to be executed by workers
+
+CREATE TABLE scan_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    scan_id UUID NOT NULL UNIQUE REFERENCES scans(id) ON DELETE CASCADE,
+    target JSONB NOT NULL, -- identifiers to scan for
+    status VARCHAR(50) NOT NULL DEFAULT 'queued', -- queued, running, succeeded, failed, cancelled
+    attempts INT NOT NULL DEFAULT 0,
+    max_attempts INT NOT NULL DEFAULT 5,
+    run_at TIMESTAMPTZ NOT NULL DEFAULT NOW(), -- not claimable before this time (used for backoff)
+    locked_by VARCHAR(255), -- worker currently holding the lease
+    lease_expires_at TIMESTAMPTZ,
+    heartbeat_at TIMESTAMPTZ,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX scan_jobs_queued_run_at_idx ON scan_jobs (run_at) WHERE status = 'queued';
+CREATE INDEX scan_jobs_running_lease_idx ON scan_jobs (lease_expires_at) WHERE status = 'running';
+
+CREATE TRIGGER set_timestamp
+BEFORE UPDATE ON scan_jobs
+FOR EACH ROW
+EXECUTE PROCEDURE trigger_set_timestamp();
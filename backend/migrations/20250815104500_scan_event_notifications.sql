@@ -0,0 +1,38 @@
+-- Publish scan status changes and new results on the scan_events channel so every
+-- API instance can stream them to clients, whoever made the change.
+
+CREATE OR REPLACE FUNCTION notify_scan_status_change()
+RETURNS TRIGGER AS $$
+BEGIN
+  PERFORM pg_notify('scan_events', json_build_object(
+    'type', 'status',
+    'scan_id', NEW.id,
+    'status', NEW.status
+  )::text);
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER notify_status_change
+AFTER UPDATE OF status ON scans
+FOR EACH ROW
+WHEN (OLD.status IS DISTINCT FROM NEW.status)
+EXECUTE PROCEDURE notify_scan_status_change();
+
+-- Results can be large, so only the id is sent; listeners load the row themselves
+CREATE OR REPLACE FUNCTION notify_scan_result_created()
+RETURNS TRIGGER AS $$
+BEGIN
+  PERFORM pg_notify('scan_events', json_build_object(
+    'type', 'result',
+    'scan_id', NEW.scan_id,
+    'result_id', NEW.id
+  )::text);
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER notify_result_created
+AFTER INSERT ON scan_results
+FOR EACH ROW
+EXECUTE PROCEDURE notify_scan_result_created();
@@ -0,0 +1,13 @@
+-- Per-source progress of a scan, so a cancelled scan can report what never finished.
+-- scans.status and scan_jobs.status may now also be 'cancelled'.
+
+CREATE TABLE scan_sources (
+    scan_id UUID NOT NULL REFERENCES scans(id) ON DELETE CASCADE,
+    source VARCHAR(255) NOT NULL,
+    status VARCHAR(50) NOT NULL DEFAULT 'pending', -- pending, running, completed, failed, cancelled
+    findings_count INT NOT NULL DEFAULT 0,
+    error TEXT,
+    started_at TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ,
+    PRIMARY KEY (scan_id, source)
+);
@@ -0,0 +1,23 @@
+-- Replace free-form status, risk level and finding type strings with enum types
+
+CREATE TYPE scan_status AS ENUM ('pending', 'in_progress', 'completed', 'failed', 'cancelled');
+CREATE TYPE risk_level AS ENUM ('low', 'medium', 'high', 'critical');
+CREATE TYPE finding_type AS ENUM ('email_leak', 'password_leak', 'social_media', 'data_broker');
+
+-- The status trigger depends on the column, so it is recreated around the type change
+DROP TRIGGER notify_status_change ON scans;
+
+ALTER TABLE scans
+    ALTER COLUMN status DROP DEFAULT,
+    ALTER COLUMN status TYPE scan_status USING status::scan_status,
+    ALTER COLUMN status SET DEFAULT 'pending';
+
+CREATE TRIGGER notify_status_change
+AFTER UPDATE OF status ON scans
+FOR EACH ROW
+WHEN (OLD.status IS DISTINCT FROM NEW.status)
+EXECUTE PROCEDURE notify_scan_status_change();
+
+ALTER TABLE scan_results
+    ALTER COLUMN risk_level TYPE risk_level USING risk_level::risk_level,
+    ALTER COLUMN finding_type TYPE finding_type USING finding_type::finding_type;
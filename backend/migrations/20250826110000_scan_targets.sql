@@ -0,0 +1,19 @@
+-- Identifiers a scan searches for. Replaces the target blob on scan_jobs.
+
+CREATE TYPE identifier_kind AS ENUM ('email', 'phone', 'username', 'name', 'address');
+
+CREATE TABLE scan_targets (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    scan_id UUID NOT NULL REFERENCES scans(id) ON DELETE CASCADE,
+    kind identifier_kind NOT NULL,
+    identifier JSONB NOT NULL, -- normalized identifier, including its kind
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX scan_targets_scan_id_idx ON scan_targets (scan_id);
+
+INSERT INTO scan_targets (scan_id, kind, identifier)
+SELECT scan_jobs.scan_id, (identifier->>'kind')::identifier_kind, identifier
+FROM scan_jobs, jsonb_array_elements(scan_jobs.target->'identifiers') AS identifier;
+
+ALTER TABLE scan_jobs DROP COLUMN target;
@@ -0,0 +1,24 @@
+-- Proof that a user owns the emails and phone numbers they scan
+
+CREATE TABLE identifier_verifications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind identifier_kind NOT NULL,
+    value VARCHAR(320) NOT NULL, -- normalized email or E.164 phone number
+    code_hash VARCHAR(255) NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    expires_at TIMESTAMPTZ NOT NULL,
+    consumed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX identifier_verifications_lookup_idx ON identifier_verifications (user_id, kind, value);
+
+CREATE TABLE verified_identifiers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind identifier_kind NOT NULL,
+    value VARCHAR(320) NOT NULL,
+    verified_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, kind, value)
+);
@@ -0,0 +1,18 @@
+-- The identity a user wants protected, scanned by default
+
+CREATE TABLE identity_profiles (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    birth_year INT CHECK (birth_year BETWEEN 1900 AND 2100),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE profile_identifiers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES identity_profiles(user_id) ON DELETE CASCADE,
+    kind identifier_kind NOT NULL,
+    identifier JSONB NOT NULL, -- normalized identifier, including its kind
+    alias BOOLEAN NOT NULL DEFAULT FALSE, -- other names the user is known by
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, identifier)
+);
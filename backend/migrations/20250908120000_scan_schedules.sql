@@ -0,0 +1,19 @@
+-- Recurring scans of a user's saved profile
+
+CREATE TYPE schedule_frequency AS ENUM ('daily', 'weekly', 'monthly', 'cron');
+
+CREATE TABLE scan_schedules (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    frequency schedule_frequency NOT NULL,
+    cron_expression VARCHAR(255), -- only for the 'cron' frequency
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    starts_at TIMESTAMPTZ NOT NULL, -- anchor that daily/weekly/monthly runs are counted from
+    next_run_at TIMESTAMPTZ NOT NULL,
+    last_run_at TIMESTAMPTZ,
+    last_scan_id UUID REFERENCES scans(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((frequency = 'cron') = (cron_expression IS NOT NULL))
+);
+
+CREATE INDEX scan_schedules_due_idx ON scan_schedules (next_run_at) WHERE enabled;
@@ -0,0 +1,18 @@
+-- Which source produced each result, and a fingerprint identifying the finding
+-- across scans. Fingerprints are computed by the application; rows from before
+-- this migration get theirs computed when read.
+
+ALTER TABLE scan_results ADD COLUMN source VARCHAR(100);
+
+UPDATE scan_results SET source = CASE
+    WHEN details ? 'broker' THEN details->>'broker'
+    WHEN details ? 'platform' THEN 'social_media'
+    WHEN finding_type = 'email_leak' THEN 'breach_db'
+    ELSE 'unknown'
+END;
+
+ALTER TABLE scan_results ALTER COLUMN source SET NOT NULL;
+
+ALTER TABLE scan_results ADD COLUMN fingerprint CHAR(64);
+
+CREATE INDEX scan_results_scan_fingerprint_idx ON scan_results (scan_id, fingerprint);
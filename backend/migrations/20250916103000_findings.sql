@@ -0,0 +1,63 @@
+-- Findings tracked across scans. A finding is one exposure, identified by its
+-- fingerprint; each scan_results row that matches it is an occurrence.
+
+CREATE TYPE finding_state AS ENUM ('open', 'acknowledged', 'removal_requested', 'resolved', 'ignored');
+
+CREATE TABLE findings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    fingerprint CHAR(64) NOT NULL,
+    source VARCHAR(100) NOT NULL,
+    finding_type finding_type NOT NULL,
+    details JSONB NOT NULL, -- as of the latest occurrence
+    risk_level risk_level NOT NULL,
+    source_link VARCHAR(2048),
+    state finding_state NOT NULL DEFAULT 'open',
+    first_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_scan_id UUID REFERENCES scans(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, fingerprint)
+);
+
+CREATE INDEX findings_user_state_idx ON findings (user_id, state);
+
+CREATE TABLE finding_state_changes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    finding_id UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    from_state finding_state, -- NULL when the finding was first recorded
+    to_state finding_state NOT NULL,
+    note TEXT,
+    changed_by UUID REFERENCES users(id) ON DELETE SET NULL, -- NULL for changes made by scans
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX finding_state_changes_finding_id_idx ON finding_state_changes (finding_id, changed_at);
+
+ALTER TABLE scan_results ADD COLUMN finding_id UUID REFERENCES findings(id) ON DELETE SET NULL;
+
+CREATE INDEX scan_results_finding_id_idx ON scan_results (finding_id);
+
+-- Results stored since fingerprints were introduced become findings; older ones
+-- have no stored fingerprint and stay unlinked.
+INSERT INTO findings (user_id, fingerprint, source, finding_type, details, risk_level, source_link,
+                      first_seen_at, last_seen_at, last_scan_id)
+SELECT DISTINCT ON (scans.user_id, scan_results.fingerprint)
+       scans.user_id, scan_results.fingerprint, scan_results.source, scan_results.finding_type,
+       scan_results.details, scan_results.risk_level, scan_results.source_link,
+       MIN(scan_results.found_at) OVER seen, MAX(scan_results.found_at) OVER seen, scan_results.scan_id
+FROM scan_results
+JOIN scans ON scans.id = scan_results.scan_id
+WHERE scan_results.fingerprint IS NOT NULL
+WINDOW seen AS (PARTITION BY scans.user_id, scan_results.fingerprint)
+ORDER BY scans.user_id, scan_results.fingerprint, scan_results.found_at DESC;
+
+INSERT INTO finding_state_changes (finding_id, from_state, to_state, changed_at)
+SELECT id, NULL, 'open', first_seen_at FROM findings;
+
+UPDATE scan_results SET finding_id = findings.id
+FROM scans, findings
+WHERE scans.id = scan_results.scan_id
+  AND findings.user_id = scans.user_id
+  AND findings.fingerprint = scan_results.fingerprint;
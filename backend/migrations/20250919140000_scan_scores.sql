@@ -0,0 +1,12 @@
+-- Privacy score of each completed scan, with the factors behind it. Scans that
+-- completed before scoring existed have no score.
+
+CREATE TABLE scan_scores (
+    scan_id UUID PRIMARY KEY REFERENCES scans(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    score INT NOT NULL CHECK (score BETWEEN 0 AND 100),
+    breakdown JSONB NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX scan_scores_user_id_idx ON scan_scores (user_id, computed_at);
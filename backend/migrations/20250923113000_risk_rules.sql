@@ -0,0 +1,4 @@
+-- Ids of the risk rules that set each result's risk level
+
+ALTER TABLE scan_results ADD COLUMN risk_rules TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE findings ADD COLUMN risk_rules TEXT[] NOT NULL DEFAULT '{}';
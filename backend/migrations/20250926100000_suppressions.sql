@@ -0,0 +1,16 @@
+-- Findings the user reported as false positives. Results with a suppressed
+-- fingerprint are dropped from the user's later scans.
+
+CREATE TABLE suppressions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    fingerprint CHAR(64) NOT NULL,
+    source TEXT NOT NULL,
+    finding_id UUID REFERENCES findings(id) ON DELETE SET NULL,
+    feedback_id UUID REFERENCES feedback(id) ON DELETE SET NULL,
+    -- Whether reporting the false positive is what ignored the finding, so withdrawing
+    -- the report only reopens findings it ignored, not ones the user ignored first
+    ignored_finding BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, fingerprint)
+);
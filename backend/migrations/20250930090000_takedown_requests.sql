@@ -0,0 +1,53 @@
+-- Removal requests sent to data brokers, the broker's responses, and escalation
+-- letters drafted when a broker lets the statutory deadline pass.
+
+CREATE TYPE takedown_channel AS ENUM ('email', 'web_form', 'postal');
+CREATE TYPE takedown_status AS ENUM ('sent', 'acknowledged', 'extended', 'completed', 'refused', 'overdue');
+CREATE TYPE takedown_response_kind AS ENUM ('acknowledged', 'extension', 'completed', 'refused', 'other');
+
+CREATE TABLE takedown_requests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    finding_id UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    broker VARCHAR(100) NOT NULL,
+    jurisdiction VARCHAR(20) NOT NULL, -- whose law the letter invoked
+    channel takedown_channel NOT NULL,
+    status takedown_status NOT NULL DEFAULT 'sent',
+    subject TEXT NOT NULL, -- the letter as sent
+    body TEXT NOT NULL,
+    sent_at TIMESTAMPTZ NOT NULL,
+    extension_days INTEGER NOT NULL DEFAULT 0 CHECK (extension_days >= 0),
+    deadline_at TIMESTAMPTZ NOT NULL, -- sent_at plus the statutory period and any extension
+    overdue_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX takedown_requests_user_idx ON takedown_requests (user_id, created_at);
+CREATE INDEX takedown_requests_open_deadline_idx ON takedown_requests (deadline_at)
+    WHERE status IN ('sent', 'acknowledged', 'extended');
+-- At most one open request per finding, so concurrent requests for the same
+-- finding can't both be recorded
+CREATE UNIQUE INDEX takedown_requests_open_finding_idx ON takedown_requests (finding_id)
+    WHERE status IN ('sent', 'acknowledged', 'extended');
+
+CREATE TABLE takedown_responses (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    request_id UUID NOT NULL REFERENCES takedown_requests(id) ON DELETE CASCADE,
+    kind takedown_response_kind NOT NULL,
+    note TEXT,
+    received_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX takedown_responses_request_idx ON takedown_responses (request_id, received_at);
+
+CREATE TABLE takedown_escalations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    request_id UUID NOT NULL UNIQUE REFERENCES takedown_requests(id) ON DELETE CASCADE,
+    template_set VARCHAR(100) NOT NULL,
+    subject TEXT NOT NULL,
+    text_body TEXT NOT NULL,
+    html_body TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
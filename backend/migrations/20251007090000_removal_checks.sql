@@ -0,0 +1,25 @@
+-- Re-scans of a broker after a takedown request, checking whether the listing is
+-- actually gone, and whether it comes back later.
+
+CREATE TYPE removal_check_outcome AS ENUM ('removed', 'listed', 'failed');
+
+ALTER TABLE takedown_requests ADD COLUMN next_check_at TIMESTAMPTZ; -- NULL once monitoring stops
+UPDATE takedown_requests SET next_check_at = deadline_at;
+
+CREATE INDEX takedown_requests_next_check_idx ON takedown_requests (next_check_at)
+    WHERE next_check_at IS NOT NULL;
+
+CREATE TABLE removal_checks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    request_id UUID NOT NULL REFERENCES takedown_requests(id) ON DELETE CASCADE,
+    finding_id UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    outcome removal_check_outcome NOT NULL,
+    evidence JSONB NOT NULL, -- what was searched and what the broker returned
+    checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX removal_checks_request_idx ON removal_checks (request_id, checked_at);
+
+ALTER TABLE findings
+    ADD COLUMN removal_verified_at TIMESTAMPTZ, -- when a removal check last found the listing gone
+    ADD COLUMN relisted BOOLEAN NOT NULL DEFAULT FALSE; -- reappeared after its removal was verified
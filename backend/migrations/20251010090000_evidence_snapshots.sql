@@ -0,0 +1,22 @@
+-- Responses captured from sources as evidence of findings. Bodies live in the
+-- content-addressed blob store; each finding's snapshots form a SHA-256 hash chain.
+
+CREATE TABLE evidence_snapshots (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    finding_id UUID NOT NULL REFERENCES findings(id) ON DELETE CASCADE,
+    scan_id UUID REFERENCES scans(id) ON DELETE SET NULL, -- NULL for removal checks
+    position INTEGER NOT NULL CHECK (position > 0), -- order within the finding's chain
+    url TEXT NOT NULL,
+    http_status SMALLINT NOT NULL,
+    headers JSONB NOT NULL, -- [[name, value], ...] in the order received
+    content_type TEXT,
+    body_hash CHAR(64) NOT NULL, -- SHA-256 of the body, its key in the blob store
+    body_size BIGINT NOT NULL,
+    captured_at TIMESTAMPTZ NOT NULL,
+    prev_hash CHAR(64), -- chain_hash of the previous snapshot, NULL for the first
+    chain_hash CHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (finding_id, position)
+);
+
+ALTER TABLE takedown_escalations ADD COLUMN evidence_ids UUID[] NOT NULL DEFAULT '{}';
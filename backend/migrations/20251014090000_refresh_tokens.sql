@@ -0,0 +1,16 @@
+-- Opaque refresh tokens, stored hashed. Each login starts a family; refreshing
+-- rotates to a new token in the same family, and reuse of a rotated token
+-- revokes the whole family.
+
+CREATE TABLE refresh_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    family_id UUID NOT NULL, -- shared by every token rotated from the same login
+    token_hash CHAR(64) NOT NULL UNIQUE, -- SHA-256 of the token
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ, -- when it was rotated
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX refresh_tokens_family_idx ON refresh_tokens (family_id);
@@ -0,0 +1,10 @@
+-- Account email verification. New accounts start unverified and can't scan
+-- until the address is confirmed through a signed link.
+
+ALTER TABLE users
+    ADD COLUMN email_verified_at TIMESTAMPTZ,
+    -- When the last verification link went out, to rate limit resends
+    ADD COLUMN verification_sent_at TIMESTAMPTZ;
+
+-- Accounts that predate verification keep working
+UPDATE users SET email_verified_at = created_at;
@@ -1,8 +1,18 @@
 // src/app_state.rs
 
+use crate::{
+    events::ScanEventHub, evidence::BlobStore, mail::templates::MailTemplates, sms::SmsSender,
+    takedown::TakedownTemplates,
+};
 use sqlx::PgPool;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db_pool: PgPool,
+    pub scan_events: ScanEventHub,
+    pub mail_templates: Arc<MailTemplates>,
+    pub sms: Arc<dyn SmsSender>,
+    pub takedown_templates: Arc<TakedownTemplates>,
+    pub blob_store: Arc<dyn BlobStore>,
 }
@@ -0,0 +1,55 @@
+// src/auth/email_verification.rs
+
+use jsonwebtoken::{decode, encode, errors::ErrorKind, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use std::env;
+
+const DEFAULT_LINK_TTL_HOURS: i64 = 24;
+/// Keeps access tokens and verification tokens from being used for each other.
+const PURPOSE: &str = "email_verification";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerificationClaims {
+    pub sub: String,   // Subject (user id)
+    pub email: String, // Address the link was sent to
+    pub purpose: String,
+    pub exp: usize,
+}
+
+/// How long a verification link is valid, from `EMAIL_VERIFICATION_TTL_HOURS`
+/// (default 24).
+pub fn verification_link_ttl() -> chrono::Duration {
+    let hours = env::var("EMAIL_VERIFICATION_TTL_HOURS")
+        .ok()
+        .and_then(|hours| hours.parse().ok())
+        .unwrap_or(DEFAULT_LINK_TTL_HOURS);
+    chrono::Duration::hours(hours)
+}
+
+/// A signed token for the verification link, tied to the address it is sent to.
+pub fn create_verification_token(user_id: &str, email: &str) -> Result<String, jsonwebtoken::errors::Error> {
+    let expiration = chrono::Utc::now()
+        .checked_add_signed(verification_link_ttl())
+        .expect("valid timestamp")
+        .timestamp();
+
+    let claims = VerificationClaims {
+        sub: user_id.to_owned(),
+        email: email.to_owned(),
+        purpose: PURPOSE.to_owned(),
+        exp: expiration as usize,
+    };
+
+    let secret = env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_ref()))
+}
+
+pub fn decode_verification_token(token: &str) -> Result<VerificationClaims, jsonwebtoken::errors::Error> {
+    let secret = env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+    let claims = decode::<VerificationClaims>(token, &DecodingKey::from_secret(secret.as_ref()), &Validation::default())?
+        .claims;
+    if claims.purpose != PURPOSE {
+        return Err(ErrorKind::InvalidToken.into());
+    }
+    Ok(claims)
+}
@@ -2,12 +2,23 @@
 
 use crate::{
     app_state::AppState,
-    auth::{jwt, password},
-    db::user_repo,
+    auth::{email_verification, jwt, password, refresh},
+    db::{outbox_repo, refresh_token_repo, session_repo, user_repo, verification_repo},
     errors::AppError,
+    models::user::User,
+    scanner::Identifier,
 };
-use axum::{extract::State, http::StatusCode, Json};
+use axum::{
+    extract::{ConnectInfo, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    Extension, Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::errors::ErrorKind;
 use serde::{Deserialize, Serialize};
+use sqlx::PgConnection;
+use std::{env, net::SocketAddr};
+use uuid::Uuid;
 use validator::Validate;
 
 #[derive(Deserialize, Validate)]
@@ -28,13 +39,50 @@ pub struct LoginRequest {
     pub password: String,
 }
 
+#[derive(Deserialize)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+/// Minimum wait before another verification link is sent to an account.
+const VERIFICATION_RESEND_INTERVAL_SECS: i64 = 300;
+
+#[derive(Deserialize)]
+pub struct VerifyEmailQuery {
+    pub token: String,
+}
+
+#[derive(Serialize)]
+pub struct EmailVerificationResponse {
+    pub email: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+pub struct VerificationSentResponse {
+    pub message: String,
+}
+
 #[derive(Serialize)]
 pub struct AuthResponse {
+    /// Short-lived access token for the `Authorization: Bearer` header.
     pub token: String,
+    /// Seconds until `token` expires.
+    pub expires_in: i64,
+    /// Exchanged at `/api/token/refresh` for new tokens; each can be used once.
+    pub refresh_token: String,
+}
+
+/// The device a login came from, kept with its session.
+struct ClientInfo {
+    user_agent: Option<String>,
+    ip_address: Option<String>,
 }
 
 pub async fn register(
     State(state): State<AppState>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
     Json(payload): Json<RegisterRequest>,
 ) -> Result<(StatusCode, Json<AuthResponse>), AppError> {
     if let Err(e) = payload.validate() {
@@ -69,17 +117,27 @@ pub async fn register(
     .await
     .map_err(|_| AppError::InternalServerError)?;
 
-    let token =
-        jwt::create_jwt(&new_user.id.to_string()).map_err(|_| AppError::InternalServerError)?;
+    // The account is usable without it, and the user can ask for another link
+    let sent = match user_repo::claim_verification_send(&state.db_pool, new_user.id, Utc::now()).await {
+        Ok(_) => queue_verification_email(&state, &new_user).await,
+        Err(e) => Err(e.to_string()),
+    };
+    if let Err(e) = sent {
+        tracing::error!("Failed to send verification email to user {}: {}", new_user.id, e);
+    }
+
+    let tokens = start_session(&state, new_user.id, client_info(&headers, peer)).await?;
 
     Ok((
         StatusCode::CREATED,
-        Json(AuthResponse { token }),
+        Json(tokens),
     ))
 }
 
 pub async fn login(
     State(state): State<AppState>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
     Json(payload): Json<LoginRequest>,
 ) -> Result<(StatusCode, Json<AuthResponse>), AppError> {
     if let Err(e) = payload.validate() {
@@ -98,7 +156,268 @@ pub async fn login(
         return Err(AppError::BadRequest("Invalid email or password".to_string()));
     }
 
-    let token = jwt::create_jwt(&user.id.to_string()).map_err(|_| AppError::InternalServerError)?;
+    let tokens = start_session(&state, user.id, client_info(&headers, peer)).await?;
+
+    Ok((StatusCode::OK, Json(tokens)))
+}
+
+/// Exchanges a refresh token for a new access token and a new refresh token in
+/// the same session. A token can only be exchanged once: presenting it again means
+/// it was copied, so the session is revoked and the user must sign in again.
+pub async fn refresh_token(
+    State(state): State<AppState>,
+    Json(payload): Json<RefreshRequest>,
+) -> Result<(StatusCode, Json<AuthResponse>), AppError> {
+    let token_hash = refresh::hash_token(payload.refresh_token.trim());
+
+    let mut tx = state.db_pool.begin().await.map_err(|_| AppError::InternalServerError)?;
+    let used = refresh_token_repo::use_refresh_token(&mut tx, &token_hash)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    let Some(used) = used else {
+        drop(tx);
+        return Err(rejected_refresh(&state, &token_hash).await);
+    };
+
+    let tokens = create_tokens(&mut tx, used.user_id, used.session_id).await?;
+    tx.commit().await.map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(tokens)))
+}
+
+/// Revokes the refresh token's session, signing that login out. Unknown tokens
+/// are accepted too, so logging out twice is harmless.
+pub async fn logout(
+    State(state): State<AppState>,
+    Json(payload): Json<RefreshRequest>,
+) -> Result<StatusCode, AppError> {
+    let token_hash = refresh::hash_token(payload.refresh_token.trim());
+    let token = refresh_token_repo::find_refresh_token(&state.db_pool, &token_hash)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    if let Some(token) = token {
+        session_repo::revoke_session(&state.db_pool, token.user_id, token.session_id)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Target of the link in account verification emails. The signed token carries
+/// the user and address, so it works without being logged in.
+pub async fn verify_email(
+    State(state): State<AppState>,
+    Query(query): Query<VerifyEmailQuery>,
+) -> Result<(StatusCode, Json<EmailVerificationResponse>), AppError> {
+    let claims = email_verification::decode_verification_token(query.token.trim()).map_err(|e| match e.kind() {
+        ErrorKind::ExpiredSignature => {
+            AppError::BadRequest("This verification link has expired, request a new one".to_string())
+        }
+        _ => AppError::BadRequest("Invalid verification link".to_string()),
+    })?;
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::BadRequest("Invalid verification link".to_string()))?;
+
+    let mut tx = state.db_pool.begin().await.map_err(|_| AppError::InternalServerError)?;
+    // Fails once the account is gone or its email changed since the link was sent
+    let user = user_repo::mark_email_verified(&mut tx, user_id, &claims.email)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .ok_or_else(|| AppError::BadRequest("This verification link is no longer valid".to_string()))?;
 
-    Ok((StatusCode::OK, Json(AuthResponse { token })))
+    // The account's own address may be scanned from now on
+    let identifier = Identifier::Email { value: user.email.clone() }.normalized();
+    if let Ok(identifier) = identifier {
+        if let Some(value) = identifier.verifiable_value() {
+            verification_repo::mark_verified(&mut tx, user.id, identifier.kind(), value)
+                .await
+                .map_err(|_| AppError::InternalServerError)?;
+        }
+    }
+    tx.commit().await.map_err(|_| AppError::InternalServerError)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(EmailVerificationResponse {
+            email: user.email,
+            email_verified_at: user.email_verified_at,
+        }),
+    ))
+}
+
+/// Sends the user a new verification link, at most once every few minutes.
+pub async fn resend_verification(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+) -> Result<(StatusCode, Json<VerificationSentResponse>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let user = user_repo::find_user_by_id(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    if user.email_verified_at.is_some() {
+        return Err(AppError::BadRequest("Email address is already verified".to_string()));
+    }
+
+    let interval = Duration::seconds(VERIFICATION_RESEND_INTERVAL_SECS);
+    let claimed = user_repo::claim_verification_send(&state.db_pool, user.id, Utc::now() - interval)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    if !claimed {
+        let wait = user
+            .verification_sent_at
+            .map(|sent_at| (sent_at + interval - Utc::now()).num_seconds())
+            .unwrap_or(0)
+            .max(1);
+        return Err(AppError::TooManyRequests(format!(
+            "A verification email was sent recently, try again in {} seconds",
+            wait
+        )));
+    }
+
+    if let Err(e) = queue_verification_email(&state, &user).await {
+        tracing::error!("Failed to send verification email to user {}: {}", user.id, e);
+        return Err(AppError::InternalServerError);
+    }
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(VerificationSentResponse {
+            message: format!("Verification email sent to {}", user.email),
+        }),
+    ))
+}
+
+/// Scanning, and having the service send codes to other identifiers, is only open
+/// to accounts whose email has been verified.
+pub(crate) async fn require_verified_email(state: &AppState, user_id: Uuid) -> Result<(), AppError> {
+    let user = user_repo::find_user_by_id(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+    if user.email_verified_at.is_none() {
+        return Err(AppError::Forbidden(
+            "Verify your email address first; follow the link we sent or request a new one".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Queues an email with a signed link that verifies the user's address.
+async fn queue_verification_email(state: &AppState, user: &User) -> Result<(), String> {
+    let token = email_verification::create_verification_token(&user.id.to_string(), &user.email)
+        .map_err(|e| e.to_string())?;
+    let base_url = env::var("APP_BASE_URL").unwrap_or_else(|_| "http://localhost:3000".into());
+    let link = format!("{}/api/verify-email?token={}", base_url.trim_end_matches('/'), token);
+    let context = serde_json::json!({
+        "username": user.username,
+        "link": link,
+        "ttl_hours": email_verification::verification_link_ttl().num_hours(),
+    });
+    let message = state
+        .mail_templates
+        .render("email_verification", &user.email, &context)
+        .map_err(|e| e.to_string())?;
+
+    let mut conn = state.db_pool.acquire().await.map_err(|e| e.to_string())?;
+    outbox_repo::enqueue_email(&mut conn, &message).await.map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Why a refresh token could not be exchanged. Reuse of a rotated token revokes
+/// its session.
+async fn rejected_refresh(state: &AppState, token_hash: &str) -> AppError {
+    let token = match refresh_token_repo::find_refresh_token(&state.db_pool, token_hash).await {
+        Ok(Some(token)) => token,
+        Ok(None) => return AppError::BadRequest("Invalid refresh token".to_string()),
+        Err(_) => return AppError::InternalServerError,
+    };
+    if token.revoked_at.is_some() {
+        return AppError::BadRequest("Refresh token has been revoked".to_string());
+    }
+    if token.used_at.is_some() {
+        tracing::warn!(
+            "Refresh token {} was reused, revoking session {}",
+            token.id,
+            token.session_id
+        );
+        if session_repo::revoke_session(&state.db_pool, token.user_id, token.session_id).await.is_err() {
+            return AppError::InternalServerError;
+        }
+        return AppError::BadRequest("Refresh token has already been used; sign in again".to_string());
+    }
+    AppError::BadRequest("Refresh token has expired".to_string())
+}
+
+/// Starts a session for a new login and issues its first tokens.
+async fn start_session(state: &AppState, user_id: Uuid, client: ClientInfo) -> Result<AuthResponse, AppError> {
+    let mut tx = state.db_pool.begin().await.map_err(|_| AppError::InternalServerError)?;
+    let session = session_repo::create_session(
+        &mut tx,
+        user_id,
+        client.user_agent.as_deref(),
+        client.ip_address.as_deref(),
+        Utc::now() + refresh::refresh_token_ttl(),
+    )
+    .await
+    .map_err(|_| AppError::InternalServerError)?;
+    let tokens = create_tokens(&mut tx, user_id, session.id).await?;
+    tx.commit().await.map_err(|_| AppError::InternalServerError)?;
+    Ok(tokens)
+}
+
+/// A new access token and a new refresh token for `session_id`, which stays
+/// alive as long as the refresh token.
+async fn create_tokens(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    session_id: Uuid,
+) -> Result<AuthResponse, AppError> {
+    let refresh_token = refresh::generate_token();
+    let expires_at = Utc::now() + refresh::refresh_token_ttl();
+    refresh_token_repo::create_refresh_token(
+        conn,
+        user_id,
+        session_id,
+        &refresh::hash_token(&refresh_token),
+        expires_at,
+    )
+    .await
+    .map_err(|_| AppError::InternalServerError)?;
+    session_repo::extend_session(conn, session_id, expires_at)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    let token = jwt::create_jwt(&user_id.to_string(), &session_id.to_string())
+        .map_err(|_| AppError::InternalServerError)?;
+    Ok(AuthResponse {
+        token,
+        expires_in: jwt::access_token_ttl().num_seconds(),
+        refresh_token,
+    })
+}
+
+/// The client's user agent and IP address. Behind a reverse proxy, set
+/// `TRUST_PROXY_HEADERS=true` to take the address from `X-Forwarded-For`.
+fn client_info(headers: &HeaderMap, peer: Option<ConnectInfo<SocketAddr>>) -> ClientInfo {
+    let header_value = |name| {
+        headers
+            .get(name)
+            .and_then(|value: &header::HeaderValue| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    let trust_proxy = env::var("TRUST_PROXY_HEADERS").map(|value| value == "true").unwrap_or(false);
+    let forwarded_for = header_value("x-forwarded-for")
+        .filter(|_| trust_proxy)
+        .and_then(|value| value.split(',').next())
+        .map(|ip| ip.trim().to_string());
+
+    ClientInfo {
+        // Capped so a client can't store an arbitrarily long header
+        user_agent: header_value(header::USER_AGENT.as_str()).map(|agent| agent.chars().take(512).collect()),
+        ip_address: forwarded_for.or_else(|| peer.map(|ConnectInfo(addr)| addr.ip().to_string())),
+    }
 }
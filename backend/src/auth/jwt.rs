@@ -4,21 +4,35 @@ use jsonwebtoken::{encode, Header, EncodingKey};
 use serde::{Deserialize, Serialize};
 use std::env;
 
+const DEFAULT_ACCESS_TOKEN_TTL_MINUTES: i64 = 15;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String, // Subject (user id)
     pub exp: usize,  // Expiration time
+    pub jti: String, // Session id, checked on every request
+}
+
+/// How long an access token is valid, from `ACCESS_TOKEN_TTL_MINUTES` (default
+/// 15). Clients get a new one with their refresh token.
+pub fn access_token_ttl() -> chrono::Duration {
+    let minutes = env::var("ACCESS_TOKEN_TTL_MINUTES")
+        .ok()
+        .and_then(|minutes| minutes.parse().ok())
+        .unwrap_or(DEFAULT_ACCESS_TOKEN_TTL_MINUTES);
+    chrono::Duration::minutes(minutes)
 }
 
-pub fn create_jwt(user_id: &str) -> Result<String, jsonwebtoken::errors::Error> {
+pub fn create_jwt(user_id: &str, session_id: &str) -> Result<String, jsonwebtoken::errors::Error> {
     let expiration = chrono::Utc::now()
-        .checked_add_signed(chrono::Duration::hours(24))
+        .checked_add_signed(access_token_ttl())
         .expect("valid timestamp")
         .timestamp();
 
     let claims = Claims {
         sub: user_id.to_owned(),
         exp: expiration as usize,
+        jti: session_id.to_owned(),
     };
 
     let secret = env::var("JWT_SECRET").expect("JWT_SECRET must be set");
@@ -8,33 +8,73 @@ use axum::{
 };
 use jsonwebtoken::{decode, Validation, DecodingKey};
 use std::env;
-use crate::{app_state::AppState, auth::jwt::Claims, errors::AppError};
+use uuid::Uuid;
+use crate::{app_state::AppState, auth::jwt::Claims, db::session_repo, errors::AppError};
 use axum::body::Body;
 
+/// The session the request's access token was issued for, next to the user id
+/// in the request extensions.
+#[derive(Debug, Clone, Copy)]
+pub struct SessionId(pub Uuid);
+
 pub async fn auth(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     mut req: Request<Body>,
     next: Next,
 ) -> Result<Response, AppError> {
-    let token = req.headers()
-        .get(header::AUTHORIZATION)
-        .and_then(|auth_header| auth_header.to_str().ok())
-        .and_then(|auth_value| {
-            if auth_value.starts_with("Bearer ") {
-                Some(auth_value[7..].to_owned())
-            } else {
-                None
-            }
-        });
+    let token = bearer_token(&req).ok_or_else(|| AppError::BadRequest("Missing token".to_string()))?;
+    let (claims, session_id) = authenticate(&state, &token).await?;
 
-    let token = token.ok_or_else(|| AppError::BadRequest("Missing token".to_string()))?;
+    req.extensions_mut().insert(SessionId(session_id));
+    req.extensions_mut().insert(claims.sub);
 
-    let secret = env::var("JWT_SECRET").expect("JWT_SECRET must be set");
-    let claims = decode::<Claims>(&token, &DecodingKey::from_secret(secret.as_ref()), &Validation::default())
-        .map_err(|_| AppError::BadRequest("Invalid token".to_string()))?
-        .claims;
+    Ok(next.run(req).await)
+}
 
-    req.extensions_mut().insert(claims.sub);
+/// Like `auth`, but lets anonymous requests through. Handlers get an
+/// `Option<String>` user id; a token that is present must still be valid.
+pub async fn optional_auth(
+    State(state): State<AppState>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Result<Response, AppError> {
+    let user_id = match bearer_token(&req) {
+        Some(token) => Some(authenticate(&state, &token).await?.0.sub),
+        None => None,
+    };
+
+    req.extensions_mut().insert(user_id);
 
     Ok(next.run(req).await)
 }
+
+fn bearer_token(req: &Request<Body>) -> Option<String> {
+    req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|auth_header| auth_header.to_str().ok())
+        .and_then(|auth_value| auth_value.strip_prefix("Bearer "))
+        .map(|token| token.to_owned())
+}
+
+/// Decodes the token and checks that its session is still live, so revoked
+/// sessions lose access before their access tokens expire.
+async fn authenticate(state: &AppState, token: &str) -> Result<(Claims, Uuid), AppError> {
+    let claims = decode_claims(token)?;
+    let (Ok(user_id), Ok(session_id)) = (Uuid::parse_str(&claims.sub), Uuid::parse_str(&claims.jti)) else {
+        return Err(AppError::BadRequest("Invalid token".to_string()));
+    };
+    let live = session_repo::touch_session(&state.db_pool, session_id, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    if !live {
+        return Err(AppError::BadRequest("Session has ended; sign in again".to_string()));
+    }
+    Ok((claims, session_id))
+}
+
+fn decode_claims(token: &str) -> Result<Claims, AppError> {
+    let secret = env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+    decode::<Claims>(token, &DecodingKey::from_secret(secret.as_ref()), &Validation::default())
+        .map(|data| data.claims)
+        .map_err(|_| AppError::BadRequest("Invalid token".to_string()))
+}
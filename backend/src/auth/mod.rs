@@ -1,6 +1,8 @@
 // src/auth/mod.rs
 
+pub mod email_verification;
 pub mod handler;
 pub mod jwt;
 pub mod middleware;
 pub mod password;
+pub mod refresh;
@@ -0,0 +1,31 @@
+// src/auth/refresh.rs
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use chrono::Duration;
+use sha2::{Digest, Sha256};
+use std::env;
+
+const DEFAULT_REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+
+/// How long a refresh token stays usable, from `REFRESH_TOKEN_TTL_DAYS` (default
+/// 30). Each refresh starts the period again.
+pub fn refresh_token_ttl() -> Duration {
+    let days = env::var("REFRESH_TOKEN_TTL_DAYS")
+        .ok()
+        .and_then(|days| days.parse().ok())
+        .unwrap_or(DEFAULT_REFRESH_TOKEN_TTL_DAYS);
+    Duration::days(days)
+}
+
+/// A new opaque refresh token: 32 random bytes, hex encoded.
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// What is stored in place of the token. The token is random enough that a plain
+/// SHA-256 needs no salt and can be looked up directly.
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -0,0 +1,90 @@
+// src/bin/shadow_scan_worker.rs
+
+// Scan worker process. Pulls scan jobs from the database queue and runs the
+// scanners, independently of the API server, enqueues scheduled scans as they
+// come due, delivers queued email, flags overdue takedown requests and re-scans
+// brokers to verify removals. Run as many replicas as needed.
+
+use shadow_scan_backend::{
+    evidence,
+    jobs::{
+        deadlines, outbox, removal, scheduler,
+        worker::{self, WorkerConfig},
+    },
+    mail, startup,
+};
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+#[tokio::main]
+async fn main() {
+    // Load environment variables from .env file
+    dotenv::dotenv().ok();
+
+    // Initialize tracing (for logging)
+    startup::init_tracing();
+
+    let config = WorkerConfig::from_env();
+
+    // One connection per concurrent job, plus headroom for heartbeats, the reaper,
+    // the scheduler, the deadline monitor, mail delivery and removal checks
+    let pool = startup::connect_db(config.concurrency as u32 * 2 + 9).await;
+
+    let scanners = startup::scanner_registry();
+    let risk_rules = Arc::new(startup::risk_rules());
+    let takedown_templates = Arc::new(startup::takedown_templates());
+    let mailer = mail::mailer_from_env();
+    let blob_store = evidence::blob_store_from_env();
+
+    // Stop claiming new jobs on Ctrl+C / SIGTERM and let running ones finish
+    let shutdown = CancellationToken::new();
+    tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            shutdown_signal().await;
+            tracing::info!("shutdown requested, finishing running jobs");
+            shutdown.cancel();
+        }
+    });
+
+    let scheduler = tokio::spawn(scheduler::run_scheduler(pool.clone(), shutdown.clone()));
+    let deadline_monitor = tokio::spawn(deadlines::run_deadline_monitor(
+        pool.clone(),
+        takedown_templates,
+        shutdown.clone(),
+    ));
+    let mail_delivery = tokio::spawn(outbox::run_mail_delivery(pool.clone(), mailer, shutdown.clone()));
+    let removal_checks = tokio::spawn(removal::run_removal_checks(
+        pool.clone(),
+        scanners.clone(),
+        blob_store.clone(),
+        shutdown.clone(),
+    ));
+    worker::run_workers(pool, scanners, risk_rules, blob_store, config, shutdown).await;
+    let _ = scheduler.await;
+    let _ = deadline_monitor.await;
+    let _ = mail_delivery.await;
+    let _ = removal_checks.await;
+    tracing::info!("worker stopped");
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
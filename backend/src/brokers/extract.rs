@@ -0,0 +1,94 @@
+// src/brokers/extract.rs
+
+// Record extraction from broker result pages. Kept free of any I/O so it can be
+// run directly against saved HTML.
+
+use crate::brokers::ExtractionRules;
+use scraper::{ElementRef, Html, Selector};
+use serde_json::{Map, Value};
+
+pub(crate) struct CompiledField<'a> {
+    name: &'a str,
+    selector: Selector,
+    attribute: Option<&'a str>,
+    all: bool,
+}
+
+pub(crate) struct CompiledRules<'a> {
+    record: Selector,
+    fields: Vec<CompiledField<'a>>,
+}
+
+pub(crate) fn compile(rules: &ExtractionRules) -> Result<CompiledRules<'_>, String> {
+    let record = parse_selector(&rules.record)?;
+    let mut fields = rules
+        .fields
+        .iter()
+        .map(|(name, rule)| {
+            Ok(CompiledField {
+                name,
+                selector: parse_selector(&rule.selector)?,
+                attribute: rule.attribute.as_deref(),
+                all: rule.all,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    fields.sort_by(|a, b| a.name.cmp(b.name));
+    Ok(CompiledRules { record, fields })
+}
+
+fn parse_selector(selector: &str) -> Result<Selector, String> {
+    Selector::parse(selector).map_err(|e| format!("invalid selector {:?}: {:?}", selector, e))
+}
+
+/// Extracts one JSON object per record on the page. Fields with no match are omitted.
+pub fn extract_records(rules: &ExtractionRules, html: &str) -> Result<Vec<Map<String, Value>>, String> {
+    let compiled = compile(rules)?;
+    let document = Html::parse_document(html);
+
+    let records = document
+        .select(&compiled.record)
+        .map(|record| {
+            let mut fields = Map::new();
+            for field in &compiled.fields {
+                let mut values = record
+                    .select(&field.selector)
+                    .filter_map(|element| read_value(element, field.attribute));
+                let value = if field.all {
+                    let values: Vec<Value> = values.map(Value::String).collect();
+                    (!values.is_empty()).then_some(Value::Array(values))
+                } else {
+                    values.next().map(Value::String)
+                };
+                if let Some(value) = value {
+                    fields.insert(field.name.to_string(), value);
+                }
+            }
+            fields
+        })
+        .filter(|fields| !fields.is_empty())
+        .collect();
+
+    Ok(records)
+}
+
+/// Whether the page carries the broker's `no_results_text`, ignoring case and how
+/// whitespace is laid out.
+pub fn shows_no_results(rules: &ExtractionRules, html: &str) -> bool {
+    let Some(text) = &rules.no_results_text else {
+        return false;
+    };
+    let normalize = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let document = Html::parse_document(html);
+    let page = document.root_element().text().collect::<Vec<_>>().join(" ");
+    normalize(&page).contains(&normalize(text))
+}
+
+fn read_value(element: ElementRef<'_>, attribute: Option<&str>) -> Option<String> {
+    let value = match attribute {
+        Some(attribute) => element.value().attr(attribute)?.trim().to_string(),
+        None => element.text().collect::<Vec<_>>().join(" "),
+    };
+    let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!value.is_empty()).then_some(value)
+}
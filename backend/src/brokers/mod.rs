@@ -0,0 +1,160 @@
+// src/brokers/mod.rs
+
+// The data-broker catalog. Each broker is described by a TOML file in the catalog
+// directory, so adding a broker is a config change rather than a code change.
+
+pub mod extract;
+
+use crate::{models::scan::RiskLevel, scanner::IdentifierKind};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+pub const DEFAULT_CATALOG_DIR: &str = "config/brokers";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Broker {
+    /// Stable identifier, also used as the scanner name.
+    pub id: String,
+    pub name: String,
+    /// Risk level assigned to records found on this broker.
+    #[serde(default = "default_risk_level")]
+    pub risk_level: RiskLevel,
+    pub opt_out_url: Option<String>,
+    /// Jurisdiction the broker operates under, e.g. "us-ca" or "eu".
+    pub jurisdiction: String,
+    pub contact_email: Option<String>,
+    pub searches: Vec<BrokerSearch>,
+    pub extract: ExtractionRules,
+}
+
+/// How to search the broker for one kind of identifier.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BrokerSearch {
+    pub kind: IdentifierKind,
+    /// URL with `{placeholder}`s filled in from the identifier, e.g. `https://example.com/email/{email}`.
+    pub url_template: String,
+    /// Extra query parameters, which may also contain placeholders.
+    #[serde(default)]
+    pub query: HashMap<String, String>,
+}
+
+/// CSS selectors used to pull records out of a search results page.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExtractionRules {
+    /// Selects each individual record (person) on the results page.
+    pub record: String,
+    /// Fields extracted relative to each record.
+    pub fields: HashMap<String, FieldRule>,
+    /// Field holding a link to the record's detail page, used as the result's source link.
+    pub link_field: Option<String>,
+    /// Text on the page the broker shows when nothing matches a search, e.g. "No
+    /// results found". Tells that page apart from one whose records couldn't be read.
+    /// A 404 always means nothing matched.
+    pub no_results_text: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldRule {
+    pub selector: String,
+    /// Read this attribute instead of the element's text.
+    pub attribute: Option<String>,
+    /// Collect every match into an array instead of taking the first one.
+    #[serde(default)]
+    pub all: bool,
+}
+
+fn default_risk_level() -> RiskLevel {
+    RiskLevel::Medium
+}
+
+#[derive(Debug)]
+pub enum CatalogError {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, toml::de::Error),
+    Invalid(String, String),
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogError::Io(path, e) => write!(f, "failed to read {}: {}", path.display(), e),
+            CatalogError::Parse(path, e) => write!(f, "failed to parse {}: {}", path.display(), e),
+            CatalogError::Invalid(broker, msg) => write!(f, "invalid broker {}: {}", broker, msg),
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+#[derive(Debug, Clone, Default)]
+pub struct BrokerCatalog {
+    pub brokers: Vec<Broker>,
+}
+
+impl BrokerCatalog {
+    /// Loads every `*.toml` file in `dir`. A missing directory yields an empty catalog.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Self, CatalogError> {
+        let dir = dir.as_ref();
+        if !dir.exists() {
+            return Ok(Self::default());
+        }
+
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+            .map_err(|e| CatalogError::Io(dir.to_path_buf(), e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+
+        let mut brokers = Vec::new();
+        for path in paths {
+            let contents = fs::read_to_string(&path).map_err(|e| CatalogError::Io(path.clone(), e))?;
+            brokers.push(Broker::from_toml(&contents).map_err(|e| match e {
+                CatalogError::Parse(_, e) => CatalogError::Parse(path.clone(), e),
+                other => other,
+            })?);
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for broker in &brokers {
+            if !seen.insert(broker.id.as_str()) {
+                return Err(CatalogError::Invalid(broker.id.clone(), "duplicate broker id".to_string()));
+            }
+        }
+
+        Ok(Self { brokers })
+    }
+}
+
+impl Broker {
+    pub fn from_toml(contents: &str) -> Result<Self, CatalogError> {
+        let broker: Broker =
+            toml::from_str(contents).map_err(|e| CatalogError::Parse(PathBuf::new(), e))?;
+        broker.validate()?;
+        Ok(broker)
+    }
+
+    fn validate(&self) -> Result<(), CatalogError> {
+        if self.searches.is_empty() {
+            return Err(CatalogError::Invalid(self.id.clone(), "no searches defined".to_string()));
+        }
+        extract::compile(&self.extract).map_err(|msg| CatalogError::Invalid(self.id.clone(), msg))?;
+        if let Some(link_field) = &self.extract.link_field {
+            if !self.extract.fields.contains_key(link_field) {
+                return Err(CatalogError::Invalid(
+                    self.id.clone(),
+                    format!("link_field {} is not an extracted field", link_field),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn search_for(&self, kind: IdentifierKind) -> Option<&BrokerSearch> {
+        self.searches.iter().find(|search| search.kind == kind)
+    }
+}
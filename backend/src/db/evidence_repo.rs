@@ -0,0 +1,116 @@
+// src/db/evidence_repo.rs
+
+use crate::{
+    evidence::{self, Capture, ChainedFields},
+    models::evidence::EvidenceSnapshot,
+};
+use chrono::DateTime;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use uuid::Uuid;
+
+fn map_snapshot(row: PgRow) -> EvidenceSnapshot {
+    EvidenceSnapshot {
+        id: row.get("id"),
+        finding_id: row.get("finding_id"),
+        scan_id: row.get("scan_id"),
+        position: row.get("position"),
+        url: row.get("url"),
+        http_status: row.get("http_status"),
+        headers: row.get("headers"),
+        content_type: row.get("content_type"),
+        body_hash: row.get("body_hash"),
+        body_size: row.get("body_size"),
+        captured_at: row.get("captured_at"),
+        prev_hash: row.get("prev_hash"),
+        chain_hash: row.get("chain_hash"),
+        created_at: row.get("created_at"),
+    }
+}
+
+/// Appends a snapshot of `capture`, whose body is stored under `body_hash`, to the
+/// finding's chain.
+pub async fn append_snapshot(
+    pool: &PgPool,
+    finding_id: Uuid,
+    scan_id: Option<Uuid>,
+    capture: &Capture,
+    body_hash: &str,
+) -> Result<EvidenceSnapshot, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    // Serializes appends to the finding's chain
+    sqlx::query("SELECT id FROM findings WHERE id = $1 FOR UPDATE")
+        .bind(finding_id)
+        .execute(&mut *tx)
+        .await?;
+    let last = sqlx::query(
+        "SELECT position, chain_hash FROM evidence_snapshots WHERE finding_id = $1 ORDER BY position DESC LIMIT 1",
+    )
+    .bind(finding_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let (position, prev_hash) = match last {
+        Some(row) => (row.get::<i32, _>("position") + 1, Some(row.get::<String, _>("chain_hash"))),
+        None => (1, None),
+    };
+
+    let headers = serde_json::to_value(&capture.headers).unwrap_or_default();
+    let body_size = capture.body.len() as i64;
+    // Stored to the microsecond, so hashed that way too
+    let captured_at = DateTime::from_timestamp_micros(capture.captured_at.timestamp_micros())
+        .unwrap_or(capture.captured_at);
+    let chain_hash = evidence::chain_hash(&ChainedFields {
+        finding_id,
+        position,
+        url: &capture.url,
+        http_status: capture.status as i16,
+        headers: &headers,
+        content_type: capture.content_type.as_deref(),
+        body_hash,
+        body_size,
+        captured_at,
+        prev_hash: prev_hash.as_deref(),
+    });
+
+    let row = sqlx::query(
+        r#"
+        INSERT INTO evidence_snapshots (finding_id, scan_id, position, url, http_status, headers, content_type, body_hash, body_size, captured_at, prev_hash, chain_hash)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        RETURNING *
+        "#
+    )
+    .bind(finding_id)
+    .bind(scan_id)
+    .bind(position)
+    .bind(&capture.url)
+    .bind(capture.status as i16)
+    .bind(&headers)
+    .bind(capture.content_type.as_deref())
+    .bind(body_hash)
+    .bind(body_size)
+    .bind(captured_at)
+    .bind(prev_hash.as_deref())
+    .bind(&chain_hash)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(map_snapshot(row))
+}
+
+/// The finding's snapshots in chain order.
+pub async fn get_snapshots(pool: &PgPool, finding_id: Uuid) -> Result<Vec<EvidenceSnapshot>, sqlx::Error> {
+    let rows = sqlx::query("SELECT * FROM evidence_snapshots WHERE finding_id = $1 ORDER BY position")
+        .bind(finding_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(map_snapshot).collect())
+}
+
+pub async fn get_snapshot(pool: &PgPool, snapshot_id: Uuid) -> Result<Option<EvidenceSnapshot>, sqlx::Error> {
+    let row = sqlx::query("SELECT * FROM evidence_snapshots WHERE id = $1")
+        .bind(snapshot_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(map_snapshot))
+}
@@ -30,6 +30,7 @@ pub async fn create_feedback(
         user_id: row.get("user_id"),
         message: row.get("message"),
         is_false_positive: row.get("is_false_positive"),
+        related_result_id: row.get("related_result_id"),
         created_at: row.get("created_at"),
     };
 
@@ -0,0 +1,273 @@
+// src/db/finding_repo.rs
+
+use crate::{
+    models::finding::{FindingState, FindingStateChange, TrackedFinding},
+    scanner::Finding,
+};
+use sqlx::{postgres::PgRow, PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+fn map_finding(row: &PgRow) -> TrackedFinding {
+    TrackedFinding {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        fingerprint: row.get("fingerprint"),
+        source: row.get("source"),
+        finding_type: row.get("finding_type"),
+        details: row.get("details"),
+        risk_level: row.get("risk_level"),
+        risk_rules: row.get("risk_rules"),
+        source_link: row.get("source_link"),
+        state: row.get("state"),
+        first_seen_at: row.get("first_seen_at"),
+        last_seen_at: row.get("last_seen_at"),
+        last_scan_id: row.get("last_scan_id"),
+        removal_verified_at: row.get("removal_verified_at"),
+        relisted: row.get("relisted"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+fn map_change(row: PgRow) -> FindingStateChange {
+    FindingStateChange {
+        id: row.get("id"),
+        finding_id: row.get("finding_id"),
+        from_state: row.get("from_state"),
+        to_state: row.get("to_state"),
+        note: row.get("note"),
+        changed_by: row.get("changed_by"),
+        changed_at: row.get("changed_at"),
+    }
+}
+
+async fn record_state_change(
+    conn: &mut PgConnection,
+    finding_id: Uuid,
+    from_state: Option<FindingState>,
+    to_state: FindingState,
+    note: Option<&str>,
+    changed_by: Option<Uuid>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO finding_state_changes (finding_id, from_state, to_state, note, changed_by)
+        VALUES ($1, $2, $3, $4, $5)
+        "#
+    )
+    .bind(finding_id)
+    .bind(from_state)
+    .bind(to_state)
+    .bind(note)
+    .bind(changed_by)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Records that `scan_id` found `finding`, creating the tracked finding on first
+/// sight and otherwise refreshing it. A resolved finding that turns up again is
+/// reopened, and flagged as relisted if its removal had been verified. Returns the
+/// tracked finding's id.
+pub async fn record_occurrence(
+    conn: &mut PgConnection,
+    scan_id: Uuid,
+    source: &str,
+    fingerprint: &str,
+    finding: &Finding,
+    risk_rules: &[String],
+) -> Result<Uuid, sqlx::Error> {
+    let created = sqlx::query(
+        r#"
+        INSERT INTO findings (user_id, fingerprint, source, finding_type, details, risk_level, risk_rules, source_link, last_scan_id)
+        SELECT user_id, $2, $3, $4, $5, $6, $7, $8, id FROM scans WHERE id = $1
+        ON CONFLICT (user_id, fingerprint) DO NOTHING
+        RETURNING id
+        "#
+    )
+    .bind(scan_id)
+    .bind(fingerprint)
+    .bind(source)
+    .bind(finding.finding_type)
+    .bind(&finding.details)
+    .bind(finding.risk_level)
+    .bind(risk_rules)
+    .bind(finding.source_link.as_deref())
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    if let Some(row) = created {
+        let finding_id: Uuid = row.get("id");
+        record_state_change(conn, finding_id, None, FindingState::Open, None, None).await?;
+        return Ok(finding_id);
+    }
+
+    let row = sqlx::query(
+        r#"
+        UPDATE findings SET
+            details = $3,
+            risk_level = $4,
+            risk_rules = $5,
+            source_link = $6,
+            state = CASE WHEN previous.state = 'resolved' THEN 'open' ELSE previous.state END,
+            relisted = findings.relisted
+                OR (previous.state = 'resolved' AND previous.removal_verified_at IS NOT NULL),
+            last_seen_at = NOW(),
+            last_scan_id = $1,
+            updated_at = NOW()
+        FROM (
+            SELECT findings.id, findings.state, findings.removal_verified_at FROM findings
+            JOIN scans ON scans.user_id = findings.user_id
+            WHERE scans.id = $1 AND findings.fingerprint = $2
+            FOR UPDATE OF findings
+        ) previous
+        WHERE findings.id = previous.id
+        RETURNING findings.id, previous.state AS previous_state, findings.relisted
+        "#
+    )
+    .bind(scan_id)
+    .bind(fingerprint)
+    .bind(&finding.details)
+    .bind(finding.risk_level)
+    .bind(risk_rules)
+    .bind(finding.source_link.as_deref())
+    .fetch_one(&mut *conn)
+    .await?;
+
+    let finding_id: Uuid = row.get("id");
+    let previous_state: FindingState = row.get("previous_state");
+    if previous_state == FindingState::Resolved {
+        let note = if row.get("relisted") {
+            "Listed again after its removal was verified"
+        } else {
+            "Found again by a later scan"
+        };
+        record_state_change(conn, finding_id, Some(previous_state), FindingState::Open, Some(note), None)
+            .await?;
+    }
+    Ok(finding_id)
+}
+
+/// The user's findings, most recently seen first, each with the number of scan
+/// results that matched it.
+pub async fn get_findings_by_user(
+    pool: &PgPool,
+    user_id: Uuid,
+    state: Option<FindingState>,
+) -> Result<Vec<(TrackedFinding, i64)>, sqlx::Error> {
+    let rows = sqlx::query(
+        r#"
+        SELECT findings.*,
+               (SELECT COUNT(*) FROM scan_results WHERE scan_results.finding_id = findings.id) AS occurrences
+        FROM findings
+        WHERE user_id = $1 AND ($2::finding_state IS NULL OR state = $2)
+        ORDER BY last_seen_at DESC, id
+        "#
+    )
+    .bind(user_id)
+    .bind(state)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.iter().map(|row| (map_finding(row), row.get("occurrences"))).collect())
+}
+
+pub async fn get_finding_by_id(pool: &PgPool, finding_id: Uuid) -> Result<Option<TrackedFinding>, sqlx::Error> {
+    let row = sqlx::query("SELECT * FROM findings WHERE id = $1")
+        .bind(finding_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.as_ref().map(map_finding))
+}
+
+pub async fn get_state_history(
+    pool: &PgPool,
+    finding_id: Uuid,
+) -> Result<Vec<FindingStateChange>, sqlx::Error> {
+    let rows = sqlx::query("SELECT * FROM finding_state_changes WHERE finding_id = $1 ORDER BY changed_at, id")
+        .bind(finding_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(map_change).collect())
+}
+
+/// Moves a finding from `from` to `to` and records the change. Returns None if the
+/// finding is no longer in `from`.
+pub async fn set_state(
+    pool: &PgPool,
+    finding_id: Uuid,
+    from: FindingState,
+    to: FindingState,
+    note: Option<&str>,
+    changed_by: Option<Uuid>,
+) -> Result<Option<TrackedFinding>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let row = sqlx::query(
+        r#"
+        UPDATE findings SET state = $3, updated_at = NOW()
+        WHERE id = $1 AND state = $2
+        RETURNING *
+        "#
+    )
+    .bind(finding_id)
+    .bind(from)
+    .bind(to)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    record_state_change(&mut tx, finding_id, Some(from), to, note, changed_by).await?;
+    tx.commit().await?;
+    Ok(Some(map_finding(&row)))
+}
+
+/// Resolves a finding whose listing a removal check found gone, unless the user
+/// ignored it, and records when the removal was verified. Returns whether the
+/// finding was updated.
+pub async fn mark_removed(conn: &mut PgConnection, finding_id: Uuid, note: &str) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        UPDATE findings SET state = 'resolved', removal_verified_at = NOW(), updated_at = NOW()
+        FROM (SELECT id, state FROM findings WHERE id = $1 FOR UPDATE) previous
+        WHERE findings.id = previous.id AND previous.state <> 'ignored'
+        RETURNING previous.state AS previous_state
+        "#
+    )
+    .bind(finding_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(row) = row else {
+        return Ok(false);
+    };
+
+    let previous_state: FindingState = row.get("previous_state");
+    if previous_state != FindingState::Resolved {
+        record_state_change(conn, finding_id, Some(previous_state), FindingState::Resolved, Some(note), None)
+            .await?;
+    }
+    Ok(true)
+}
+
+/// Reopens a resolved finding that a removal check found listed again, flagging
+/// it as relisted. Returns whether the finding was resolved.
+pub async fn mark_relisted(conn: &mut PgConnection, finding_id: Uuid, note: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        UPDATE findings SET state = 'open', relisted = TRUE, last_seen_at = NOW(), updated_at = NOW()
+        WHERE id = $1 AND state = 'resolved'
+        "#
+    )
+    .bind(finding_id)
+    .execute(&mut *conn)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    record_state_change(conn, finding_id, Some(FindingState::Resolved), FindingState::Open, Some(note), None)
+        .await?;
+    Ok(true)
+}
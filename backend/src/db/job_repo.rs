@@ -0,0 +1,239 @@
+// src/db/job_repo.rs
+
+use crate::{
+    models::{
+        job::ScanJob,
+        scan::{Scan, ScanStatus},
+    },
+    scanner::Identifier,
+};
+use sqlx::{postgres::PgRow, types::Json, PgConnection, PgPool, Row};
+use std::time::Duration;
+use uuid::Uuid;
+
+fn map_job(row: PgRow) -> ScanJob {
+    ScanJob {
+        id: row.get("id"),
+        scan_id: row.get("scan_id"),
+        status: row.get("status"),
+        attempts: row.get("attempts"),
+        max_attempts: row.get("max_attempts"),
+        run_at: row.get("run_at"),
+        locked_by: row.get("locked_by"),
+        lease_expires_at: row.get("lease_expires_at"),
+        heartbeat_at: row.get("heartbeat_at"),
+        last_error: row.get("last_error"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+/// Creates a pending scan for the given identifiers together with the job that
+/// will execute it, atomically.
+pub async fn enqueue_scan(
+    pool: &PgPool,
+    user_id: Uuid,
+    identifiers: &[Identifier],
+) -> Result<Scan, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let scan = enqueue_scan_in(&mut tx, user_id, identifiers).await?;
+    tx.commit().await?;
+    Ok(scan)
+}
+
+/// Like `enqueue_scan`, as part of the caller's transaction.
+pub async fn enqueue_scan_in(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    identifiers: &[Identifier],
+) -> Result<Scan, sqlx::Error> {
+    let row = sqlx::query("INSERT INTO scans (user_id) VALUES ($1) RETURNING *")
+        .bind(user_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    let scan = Scan {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        status: row.get("status"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    };
+
+    for identifier in identifiers {
+        sqlx::query("INSERT INTO scan_targets (scan_id, kind, identifier) VALUES ($1, $2, $3)")
+            .bind(scan.id)
+            .bind(identifier.kind())
+            .bind(Json(identifier))
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    sqlx::query("INSERT INTO scan_jobs (scan_id) VALUES ($1)")
+        .bind(scan.id)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(scan)
+}
+
+/// Claims the oldest runnable job for `worker_id`, holding it for `lease`.
+/// Concurrent workers skip rows that are already being claimed.
+pub async fn claim_next_job(
+    pool: &PgPool,
+    worker_id: &str,
+    lease: Duration,
+) -> Result<Option<ScanJob>, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        UPDATE scan_jobs
+        SET status = 'running',
+            attempts = attempts + 1,
+            locked_by = $1,
+            lease_expires_at = NOW() + make_interval(secs => $2),
+            heartbeat_at = NOW()
+        WHERE id = (
+            SELECT id FROM scan_jobs
+            WHERE status = 'queued' AND run_at <= NOW()
+            ORDER BY run_at
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING *
+        "#
+    )
+    .bind(worker_id)
+    .bind(lease.as_secs_f64())
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(map_job))
+}
+
+/// Extends the lease on a running job and returns the current status of its scan,
+/// or None if the worker no longer holds the lease.
+pub async fn heartbeat(
+    pool: &PgPool,
+    job_id: Uuid,
+    worker_id: &str,
+    lease: Duration,
+) -> Result<Option<ScanStatus>, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        UPDATE scan_jobs
+        SET heartbeat_at = NOW(), lease_expires_at = NOW() + make_interval(secs => $3)
+        WHERE id = $1 AND locked_by = $2 AND status = 'running'
+        RETURNING (SELECT status FROM scans WHERE scans.id = scan_jobs.scan_id) AS scan_status
+        "#
+    )
+    .bind(job_id)
+    .bind(worker_id)
+    .bind(lease.as_secs_f64())
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|row| row.get("scan_status")))
+}
+
+/// Marks a job as finished with the given final status ("succeeded" or "cancelled").
+pub async fn complete_job(
+    pool: &PgPool,
+    job_id: Uuid,
+    worker_id: &str,
+    status: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE scan_jobs
+        SET status = $3, locked_by = NULL, lease_expires_at = NULL, last_error = NULL
+        WHERE id = $1 AND locked_by = $2
+        "#
+    )
+    .bind(job_id)
+    .bind(worker_id)
+    .bind(status)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Records a failed attempt. The job is re-queued after `retry_after` unless it has
+/// used up its attempts, in which case it and its scan are marked failed.
+/// Returns true if the job will be retried.
+pub async fn fail_job(
+    pool: &PgPool,
+    job_id: Uuid,
+    worker_id: &str,
+    error: &str,
+    retry_after: Duration,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let row = sqlx::query(
+        r#"
+        UPDATE scan_jobs
+        SET status = CASE WHEN attempts < max_attempts THEN 'queued' ELSE 'failed' END,
+            run_at = NOW() + make_interval(secs => $4),
+            locked_by = NULL,
+            lease_expires_at = NULL,
+            last_error = $3
+        WHERE id = $1 AND locked_by = $2
+        RETURNING scan_id, status
+        "#
+    )
+    .bind(job_id)
+    .bind(worker_id)
+    .bind(error)
+    .bind(retry_after.as_secs_f64())
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let retrying = match row {
+        Some(row) => {
+            let status: String = row.get("status");
+            if status == "failed" {
+                let scan_id: Uuid = row.get("scan_id");
+                sqlx::query("UPDATE scans SET status = $2 WHERE id = $1 AND status = ANY($3)")
+                    .bind(scan_id)
+                    .bind(ScanStatus::Failed)
+                    .bind(ScanStatus::Failed.allowed_predecessors())
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            status == "queued"
+        }
+        None => false,
+    };
+
+    tx.commit().await?;
+    Ok(retrying)
+}
+
+/// Puts jobs whose lease expired (their worker died or lost the database) back on
+/// the queue, or fails them and their scan if they have no attempts left.
+/// Returns the number of recovered jobs.
+pub async fn recover_orphaned_jobs(pool: &PgPool) -> Result<i64, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        WITH recovered AS (
+            UPDATE scan_jobs
+            SET status = CASE WHEN attempts < max_attempts THEN 'queued' ELSE 'failed' END,
+                locked_by = NULL,
+                lease_expires_at = NULL,
+                run_at = NOW(),
+                last_error = 'lease expired'
+            WHERE status = 'running' AND lease_expires_at < NOW()
+            RETURNING scan_id, status
+        ),
+        failed_scans AS (
+            UPDATE scans SET status = $1
+            WHERE id IN (SELECT scan_id FROM recovered WHERE status = 'failed')
+              AND status = ANY($2)
+        )
+        SELECT COUNT(*) AS recovered FROM recovered
+        "#
+    )
+    .bind(ScanStatus::Failed)
+    .bind(ScanStatus::Failed.allowed_predecessors())
+    .fetch_one(pool)
+    .await?;
+    Ok(row.get("recovered"))
+}
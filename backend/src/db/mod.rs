@@ -1,6 +1,18 @@
 // src/db/mod.rs
 
+pub mod evidence_repo;
 pub mod feedback_repo;
+pub mod finding_repo;
+pub mod job_repo;
+pub mod outbox_repo;
+pub mod profile_repo;
+pub mod refresh_token_repo;
 pub mod scan_repo;
+pub mod session_repo;
+pub mod schedule_repo;
+pub mod score_repo;
+pub mod suppression_repo;
+pub mod takedown_repo;
 pub mod schema;
 pub mod user_repo;
+pub mod verification_repo;
@@ -0,0 +1,114 @@
+// src/db/outbox_repo.rs
+
+use crate::{mail::EmailMessage, models::outbox::QueuedEmail};
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+fn map_email(row: PgRow) -> QueuedEmail {
+    QueuedEmail {
+        id: row.get("id"),
+        recipient: row.get("recipient"),
+        reply_to: row.get("reply_to"),
+        subject: row.get("subject"),
+        text_body: row.get("text_body"),
+        html_body: row.get("html_body"),
+        status: row.get("status"),
+        attempts: row.get("attempts"),
+        next_attempt_at: row.get("next_attempt_at"),
+        last_error: row.get("last_error"),
+        sent_at: row.get("sent_at"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+/// Queues a message for the worker to deliver.
+pub async fn enqueue_email(conn: &mut PgConnection, message: &EmailMessage) -> Result<QueuedEmail, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO email_outbox (recipient, reply_to, subject, text_body, html_body)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#
+    )
+    .bind(&message.to)
+    .bind(message.reply_to.as_deref())
+    .bind(&message.subject)
+    .bind(&message.text_body)
+    .bind(message.html_body.as_deref())
+    .fetch_one(conn)
+    .await?;
+
+    Ok(map_email(row))
+}
+
+pub async fn get_email(pool: &PgPool, email_id: Uuid) -> Result<Option<QueuedEmail>, sqlx::Error> {
+    let row = sqlx::query("SELECT * FROM email_outbox WHERE id = $1")
+        .bind(email_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(map_email))
+}
+
+/// Locks the pending message that has waited longest for an attempt, skipping
+/// any another worker is delivering.
+pub async fn lock_due_email(conn: &mut PgConnection) -> Result<Option<QueuedEmail>, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        SELECT * FROM email_outbox
+        WHERE status = 'pending' AND next_attempt_at <= NOW()
+        ORDER BY next_attempt_at
+        LIMIT 1
+        FOR UPDATE SKIP LOCKED
+        "#
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(row.map(map_email))
+}
+
+pub async fn mark_sent(conn: &mut PgConnection, email_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE email_outbox SET
+            status = 'sent',
+            attempts = attempts + 1,
+            last_error = NULL,
+            sent_at = NOW(),
+            updated_at = NOW()
+        WHERE id = $1
+        "#
+    )
+    .bind(email_id)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Records a failed attempt. The message is retried at `retry_at`, or marked
+/// failed for good without one.
+pub async fn record_failure(
+    conn: &mut PgConnection,
+    email_id: Uuid,
+    error: &str,
+    retry_at: Option<DateTime<Utc>>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE email_outbox SET
+            status = CASE WHEN $3::timestamptz IS NULL THEN 'failed'::email_status ELSE 'pending'::email_status END,
+            attempts = attempts + 1,
+            next_attempt_at = COALESCE($3, next_attempt_at),
+            last_error = $2,
+            updated_at = NOW()
+        WHERE id = $1
+        "#
+    )
+    .bind(email_id)
+    .bind(error)
+    .bind(retry_at)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
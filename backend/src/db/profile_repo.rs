@@ -0,0 +1,157 @@
+// src/db/profile_repo.rs
+
+use crate::{
+    models::profile::{IdentityProfile, ProfileIdentifier},
+    scanner::Identifier,
+};
+use sqlx::{postgres::PgRow, types::Json, PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+fn map_profile(row: PgRow) -> IdentityProfile {
+    IdentityProfile {
+        user_id: row.get("user_id"),
+        birth_year: row.get("birth_year"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+fn map_identifier(row: PgRow) -> ProfileIdentifier {
+    ProfileIdentifier {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        identifier: row.get::<Json<_>, _>("identifier").0,
+        alias: row.get("alias"),
+        created_at: row.get("created_at"),
+    }
+}
+
+pub async fn get_profile(pool: &PgPool, user_id: Uuid) -> Result<Option<IdentityProfile>, sqlx::Error> {
+    let row = sqlx::query("SELECT * FROM identity_profiles WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(map_profile))
+}
+
+pub async fn get_profile_identifiers(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<ProfileIdentifier>, sqlx::Error> {
+    let rows = sqlx::query("SELECT * FROM profile_identifiers WHERE user_id = $1 ORDER BY created_at, id")
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(map_identifier).collect())
+}
+
+/// Creates the profile if needed, or bumps its `updated_at`.
+async fn touch_profile(conn: &mut PgConnection, user_id: Uuid) -> Result<IdentityProfile, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO identity_profiles (user_id) VALUES ($1)
+        ON CONFLICT (user_id) DO UPDATE SET updated_at = NOW()
+        RETURNING *
+        "#
+    )
+    .bind(user_id)
+    .fetch_one(conn)
+    .await?;
+    Ok(map_profile(row))
+}
+
+async fn insert_identifier(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    identifier: &Identifier,
+    alias: bool,
+) -> Result<ProfileIdentifier, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO profile_identifiers (user_id, kind, identifier, alias)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#
+    )
+    .bind(user_id)
+    .bind(identifier.kind())
+    .bind(Json(identifier))
+    .bind(alias)
+    .fetch_one(conn)
+    .await?;
+    Ok(map_identifier(row))
+}
+
+/// Replaces the whole profile. `identifiers` pairs each identifier with its alias flag
+/// and must not contain duplicates.
+pub async fn save_profile(
+    pool: &PgPool,
+    user_id: Uuid,
+    birth_year: Option<i32>,
+    identifiers: &[(Identifier, bool)],
+) -> Result<(IdentityProfile, Vec<ProfileIdentifier>), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let row = sqlx::query(
+        r#"
+        INSERT INTO identity_profiles (user_id, birth_year) VALUES ($1, $2)
+        ON CONFLICT (user_id) DO UPDATE SET birth_year = EXCLUDED.birth_year, updated_at = NOW()
+        RETURNING *
+        "#
+    )
+    .bind(user_id)
+    .bind(birth_year)
+    .fetch_one(&mut *tx)
+    .await?;
+    let profile = map_profile(row);
+
+    sqlx::query("DELETE FROM profile_identifiers WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let mut saved = Vec::with_capacity(identifiers.len());
+    for (identifier, alias) in identifiers {
+        saved.push(insert_identifier(&mut tx, user_id, identifier, *alias).await?);
+    }
+
+    tx.commit().await?;
+    Ok((profile, saved))
+}
+
+/// Adds one identifier, creating the profile if the user has none yet.
+pub async fn add_profile_identifier(
+    pool: &PgPool,
+    user_id: Uuid,
+    identifier: &Identifier,
+    alias: bool,
+) -> Result<ProfileIdentifier, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    touch_profile(&mut tx, user_id).await?;
+    let saved = insert_identifier(&mut tx, user_id, identifier, alias).await?;
+    tx.commit().await?;
+    Ok(saved)
+}
+
+pub async fn delete_profile_identifier(pool: &PgPool, user_id: Uuid, id: Uuid) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let result = sqlx::query("DELETE FROM profile_identifiers WHERE id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+    if result.rows_affected() == 0 {
+        return Ok(false);
+    }
+    touch_profile(&mut tx, user_id).await?;
+    tx.commit().await?;
+    Ok(true)
+}
+
+pub async fn delete_profile(pool: &PgPool, user_id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM identity_profiles WHERE user_id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
@@ -0,0 +1,66 @@
+// src/db/refresh_token_repo.rs
+
+use crate::models::refresh_token::RefreshToken;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+fn map_token(row: PgRow) -> RefreshToken {
+    RefreshToken {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        session_id: row.get("session_id"),
+        token_hash: row.get("token_hash"),
+        expires_at: row.get("expires_at"),
+        used_at: row.get("used_at"),
+        revoked_at: row.get("revoked_at"),
+        created_at: row.get("created_at"),
+    }
+}
+
+pub async fn create_refresh_token(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    session_id: Uuid,
+    token_hash: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<RefreshToken, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO refresh_tokens (user_id, session_id, token_hash, expires_at)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#
+    )
+    .bind(user_id)
+    .bind(session_id)
+    .bind(token_hash)
+    .bind(expires_at)
+    .fetch_one(conn)
+    .await?;
+    Ok(map_token(row))
+}
+
+/// Marks the token used if it is still live, i.e. unused, unrevoked and
+/// unexpired. Returns None otherwise, so only one exchange of a token succeeds.
+pub async fn use_refresh_token(conn: &mut PgConnection, token_hash: &str) -> Result<Option<RefreshToken>, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        UPDATE refresh_tokens SET used_at = NOW()
+        WHERE token_hash = $1 AND used_at IS NULL AND revoked_at IS NULL AND expires_at > NOW()
+        RETURNING *
+        "#
+    )
+    .bind(token_hash)
+    .fetch_optional(conn)
+    .await?;
+    Ok(row.map(map_token))
+}
+
+pub async fn find_refresh_token(pool: &PgPool, token_hash: &str) -> Result<Option<RefreshToken>, sqlx::Error> {
+    let row = sqlx::query("SELECT * FROM refresh_tokens WHERE token_hash = $1")
+        .bind(token_hash)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(map_token))
+}
@@ -1,75 +1,223 @@
 // src/db/scan_repo.rs
 
-use crate::models::scan::{Scan, ScanResult};
-use sqlx::{PgPool, Row};
+use crate::{
+    db::finding_repo,
+    findings::fingerprint,
+    models::scan::{Scan, ScanIdentifier, ScanResult, ScanSource, ScanStatus},
+    scanner::Finding,
+};
+use sqlx::{postgres::PgRow, types::Json, PgConnection, PgPool, Row};
+use std::fmt;
 use uuid::Uuid;
 
-pub async fn create_scan(pool: &PgPool, user_id: Uuid) -> Result<Scan, sqlx::Error> {
-    let row = sqlx::query("INSERT INTO scans (user_id) VALUES ($1) RETURNING *")
-        .bind(user_id)
-        .fetch_one(pool)
+#[derive(Debug)]
+pub enum StatusUpdateError {
+    NotFound,
+    /// The scan's current status does not allow moving to the requested one.
+    IllegalTransition { from: ScanStatus, to: ScanStatus },
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for StatusUpdateError {
+    fn from(e: sqlx::Error) -> Self {
+        StatusUpdateError::Database(e)
+    }
+}
+
+impl fmt::Display for StatusUpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatusUpdateError::NotFound => f.write_str("scan not found"),
+            StatusUpdateError::IllegalTransition { from, to } => {
+                write!(f, "illegal scan status transition from {} to {}", from, to)
+            }
+            StatusUpdateError::Database(e) => write!(f, "database error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for StatusUpdateError {}
+
+pub async fn get_scan_by_id(pool: &PgPool, scan_id: Uuid) -> Result<Option<Scan>, sqlx::Error> {
+    let row = sqlx::query("SELECT * FROM scans WHERE id = $1")
+        .bind(scan_id)
+        .fetch_optional(pool)
         .await?;
 
-    let scan = Scan {
+    let scan = row.map(|row| Scan {
         id: row.get("id"),
         user_id: row.get("user_id"),
         status: row.get("status"),
         created_at: row.get("created_at"),
         updated_at: row.get("updated_at"),
-    };
+    });
     Ok(scan)
 }
 
+/// Moves a scan to `status`, enforcing the scan state machine
+/// (pending → in_progress → completed/failed/cancelled).
 pub async fn update_scan_status(
     pool: &PgPool,
     scan_id: Uuid,
-    status: &str,
-) -> Result<(), sqlx::Error> {
-    sqlx::query("UPDATE scans SET status = $1 WHERE id = $2")
+    status: ScanStatus,
+) -> Result<Scan, StatusUpdateError> {
+    let mut conn = pool.acquire().await?;
+    update_scan_status_in(&mut conn, scan_id, status).await
+}
+
+/// Like `update_scan_status`, as part of the caller's transaction.
+pub async fn update_scan_status_in(
+    conn: &mut PgConnection,
+    scan_id: Uuid,
+    status: ScanStatus,
+) -> Result<Scan, StatusUpdateError> {
+    let row = sqlx::query("UPDATE scans SET status = $1 WHERE id = $2 AND status = ANY($3) RETURNING *")
         .bind(status)
         .bind(scan_id)
-        .execute(pool)
+        .bind(status.allowed_predecessors())
+        .fetch_optional(&mut *conn)
         .await?;
-    Ok(())
+
+    match row {
+        Some(row) => Ok(Scan {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            status: row.get("status"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }),
+        None => {
+            let current = sqlx::query("SELECT status FROM scans WHERE id = $1")
+                .bind(scan_id)
+                .fetch_optional(&mut *conn)
+                .await?;
+            match current {
+                Some(current) => Err(StatusUpdateError::IllegalTransition {
+                    from: current.get("status"),
+                    to: status,
+                }),
+                None => Err(StatusUpdateError::NotFound),
+            }
+        }
+    }
 }
 
-pub async fn create_scan_result(
+/// Cancels a user's scan if it has not finished yet, together with its job if no
+/// worker has picked it up. Returns None if there is no such unfinished scan.
+pub async fn cancel_scan(
     pool: &PgPool,
     scan_id: Uuid,
-    finding_type: &str,
-    details: serde_json::Value,
-    risk_level: &str,
-    source_link: Option<&str>,
-) -> Result<ScanResult, sqlx::Error> {
+    user_id: Uuid,
+) -> Result<Option<Scan>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
     let row = sqlx::query(
         r#"
-        INSERT INTO scan_results (scan_id, finding_type, details, risk_level, source_link)
-        VALUES ($1, $2, $3, $4, $5)
-        RETURNING id, scan_id, finding_type, details, risk_level, source_link, found_at
+        UPDATE scans SET status = $3
+        WHERE id = $1 AND user_id = $2 AND status = ANY($4)
+        RETURNING *
         "#
     )
     .bind(scan_id)
-    .bind(finding_type)
-    .bind(details)
-    .bind(risk_level)
-    .bind(source_link)
-    .fetch_one(pool)
+    .bind(user_id)
+    .bind(ScanStatus::Cancelled)
+    .bind(ScanStatus::Cancelled.allowed_predecessors())
+    .fetch_optional(&mut *tx)
     .await?;
 
-    let result = ScanResult {
+    let scan = row.map(|row| Scan {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        status: row.get("status"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    });
+
+    if scan.is_some() {
+        sqlx::query("UPDATE scan_jobs SET status = 'cancelled' WHERE scan_id = $1 AND status = 'queued'")
+            .bind(scan_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+    Ok(scan)
+}
+
+fn map_result(row: PgRow) -> ScanResult {
+    let source: String = row.get("source");
+    let details: serde_json::Value = row.get("details");
+    let fingerprint = row
+        .get::<Option<String>, _>("fingerprint")
+        .unwrap_or_else(|| fingerprint::fingerprint(&source, &details));
+    ScanResult {
         id: row.get("id"),
         scan_id: row.get("scan_id"),
+        source,
+        fingerprint,
+        finding_id: row.get("finding_id"),
         finding_type: row.get("finding_type"),
-        details: row.get("details"),
+        details,
         risk_level: row.get("risk_level"),
+        risk_rules: row.get("risk_rules"),
         source_link: row.get("source_link"),
         found_at: row.get("found_at"),
-    };
-    Ok(result)
+    }
+}
+
+/// Stores a finding reported by the scanner named `source` as an occurrence of the
+/// user's tracked finding with the same fingerprint.
+pub async fn create_scan_result(
+    pool: &PgPool,
+    scan_id: Uuid,
+    source: &str,
+    finding: &Finding,
+    risk_rules: &[String],
+) -> Result<ScanResult, sqlx::Error> {
+    let fingerprint = fingerprint::fingerprint(source, &finding.details);
+
+    let mut tx = pool.begin().await?;
+    let finding_id = finding_repo::record_occurrence(&mut tx, scan_id, source, &fingerprint, finding, risk_rules).await?;
+
+    let row = sqlx::query(
+        r#"
+        INSERT INTO scan_results (scan_id, source, fingerprint, finding_id, finding_type, details, risk_level, risk_rules, source_link)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING *
+        "#
+    )
+    .bind(scan_id)
+    .bind(source)
+    .bind(&fingerprint)
+    .bind(finding_id)
+    .bind(finding.finding_type)
+    .bind(&finding.details)
+    .bind(finding.risk_level)
+    .bind(risk_rules)
+    .bind(finding.source_link.as_deref())
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(map_result(row))
+}
+
+/// Removes results and source progress left behind by an earlier, interrupted
+/// attempt at the scan.
+pub async fn delete_scan_results(pool: &PgPool, scan_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM scan_results WHERE scan_id = $1")
+        .bind(scan_id)
+        .execute(pool)
+        .await?;
+    sqlx::query("DELETE FROM scan_sources WHERE scan_id = $1")
+        .bind(scan_id)
+        .execute(pool)
+        .await?;
+    Ok(())
 }
 
 pub async fn get_scans_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Scan>, sqlx::Error> {
-    let rows = sqlx::query("SELECT * FROM scans WHERE user_id = $1")
+    let rows = sqlx::query("SELECT * FROM scans WHERE user_id = $1 ORDER BY created_at")
         .bind(user_id)
         .fetch_all(pool)
         .await?;
@@ -84,29 +232,183 @@ pub async fn get_scans_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Scan>
     Ok(scans)
 }
 
+/// The user's most recent completed scan created before `scan`, the natural
+/// baseline for what changed in it.
+pub async fn get_previous_completed_scan(pool: &PgPool, scan: &Scan) -> Result<Option<Scan>, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        SELECT * FROM scans
+        WHERE user_id = $1 AND status = 'completed' AND created_at < $2
+        ORDER BY created_at DESC
+        LIMIT 1
+        "#
+    )
+    .bind(scan.user_id)
+    .bind(scan.created_at)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(|row| Scan {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        status: row.get("status"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }))
+}
+
+/// Whether the user has a scan that is queued or running.
+pub async fn has_active_scan(pool: &PgPool, user_id: Uuid) -> Result<bool, sqlx::Error> {
+    let active: Vec<ScanStatus> = ScanStatus::ALL.into_iter().filter(|s| !s.is_terminal()).collect();
+    let row = sqlx::query("SELECT EXISTS (SELECT 1 FROM scans WHERE user_id = $1 AND status = ANY($2)) AS active")
+        .bind(user_id)
+        .bind(active)
+        .fetch_one(pool)
+        .await?;
+    Ok(row.get("active"))
+}
+
 pub async fn get_scan_results_by_scan(
     pool: &PgPool,
     scan_id: Uuid,
 ) -> Result<Vec<ScanResult>, sqlx::Error> {
     let rows = sqlx::query(
         r#"
-        SELECT id, scan_id, finding_type, details, risk_level, source_link, found_at
-        FROM scan_results
+        SELECT * FROM scan_results
         WHERE scan_id = $1
+        ORDER BY found_at, id
+        "#
+    )
+    .bind(scan_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(map_result).collect())
+}
+
+/// Every scan result that was an occurrence of the given tracked finding.
+pub async fn get_scan_results_by_finding(
+    pool: &PgPool,
+    finding_id: Uuid,
+) -> Result<Vec<ScanResult>, sqlx::Error> {
+    let rows = sqlx::query("SELECT * FROM scan_results WHERE finding_id = $1 ORDER BY found_at, id")
+        .bind(finding_id)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows.into_iter().map(map_result).collect())
+}
+
+pub async fn get_scan_result_by_id(
+    pool: &PgPool,
+    result_id: Uuid,
+) -> Result<Option<ScanResult>, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        SELECT * FROM scan_results
+        WHERE id = $1
+        "#
+    )
+    .bind(result_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(map_result))
+}
+
+/// Records the sources a scan is about to run, all as pending.
+pub async fn create_scan_sources(
+    pool: &PgPool,
+    scan_id: Uuid,
+    sources: &[String],
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO scan_sources (scan_id, source)
+        SELECT $1, UNNEST($2::VARCHAR[])
+        ON CONFLICT DO NOTHING
+        "#
+    )
+    .bind(scan_id)
+    .bind(sources)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn update_scan_source(
+    pool: &PgPool,
+    scan_id: Uuid,
+    source: &str,
+    status: &str,
+    findings_count: i32,
+    error: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE scan_sources
+        SET status = $3,
+            findings_count = $4,
+            error = $5,
+            started_at = CASE WHEN $3 = 'running' THEN NOW() ELSE started_at END,
+            finished_at = CASE WHEN $3 IN ('completed', 'failed', 'cancelled') THEN NOW() ELSE finished_at END
+        WHERE scan_id = $1 AND source = $2
+        "#
+    )
+    .bind(scan_id)
+    .bind(source)
+    .bind(status)
+    .bind(findings_count)
+    .bind(error)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Marks every source that has not finished as cancelled. Returns their names.
+pub async fn cancel_unfinished_sources(pool: &PgPool, scan_id: Uuid) -> Result<Vec<String>, sqlx::Error> {
+    let rows = sqlx::query(
+        r#"
+        UPDATE scan_sources SET status = 'cancelled', finished_at = NOW()
+        WHERE scan_id = $1 AND status IN ('pending', 'running')
+        RETURNING source
         "#
     )
     .bind(scan_id)
     .fetch_all(pool)
     .await?;
+    Ok(rows.into_iter().map(|row| row.get("source")).collect())
+}
 
-    let results = rows.into_iter().map(|row| ScanResult {
+pub async fn get_scan_sources(pool: &PgPool, scan_id: Uuid) -> Result<Vec<ScanSource>, sqlx::Error> {
+    let rows = sqlx::query("SELECT * FROM scan_sources WHERE scan_id = $1 ORDER BY source")
+        .bind(scan_id)
+        .fetch_all(pool)
+        .await?;
+
+    let sources = rows.into_iter().map(|row| ScanSource {
+        scan_id: row.get("scan_id"),
+        source: row.get("source"),
+        status: row.get("status"),
+        findings_count: row.get("findings_count"),
+        error: row.get("error"),
+        started_at: row.get("started_at"),
+        finished_at: row.get("finished_at"),
+    }).collect();
+    Ok(sources)
+}
+
+pub async fn get_scan_targets(pool: &PgPool, scan_id: Uuid) -> Result<Vec<ScanIdentifier>, sqlx::Error> {
+    let rows = sqlx::query("SELECT * FROM scan_targets WHERE scan_id = $1 ORDER BY created_at, id")
+        .bind(scan_id)
+        .fetch_all(pool)
+        .await?;
+
+    let targets = rows.into_iter().map(|row| ScanIdentifier {
         id: row.get("id"),
         scan_id: row.get("scan_id"),
-        finding_type: row.get("finding_type"),
-        details: row.get("details"),
-        risk_level: row.get("risk_level"),
-        source_link: row.get("source_link"),
-        found_at: row.get("found_at"),
+        identifier: row.get::<Json<_>, _>("identifier").0,
+        created_at: row.get("created_at"),
     }).collect();
-    Ok(results)
+    Ok(targets)
 }
@@ -0,0 +1,122 @@
+// src/db/schedule_repo.rs
+
+use crate::models::schedule::{ScanSchedule, ScheduleFrequency};
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+fn map_schedule(row: PgRow) -> ScanSchedule {
+    ScanSchedule {
+        user_id: row.get("user_id"),
+        frequency: row.get("frequency"),
+        cron_expression: row.get("cron_expression"),
+        enabled: row.get("enabled"),
+        starts_at: row.get("starts_at"),
+        next_run_at: row.get("next_run_at"),
+        last_run_at: row.get("last_run_at"),
+        last_scan_id: row.get("last_scan_id"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+pub async fn get_schedule(pool: &PgPool, user_id: Uuid) -> Result<Option<ScanSchedule>, sqlx::Error> {
+    let row = sqlx::query("SELECT * FROM scan_schedules WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(map_schedule))
+}
+
+pub async fn upsert_schedule(
+    pool: &PgPool,
+    user_id: Uuid,
+    frequency: ScheduleFrequency,
+    cron_expression: Option<&str>,
+    enabled: bool,
+    starts_at: DateTime<Utc>,
+    next_run_at: DateTime<Utc>,
+) -> Result<ScanSchedule, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO scan_schedules (user_id, frequency, cron_expression, enabled, starts_at, next_run_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (user_id) DO UPDATE SET
+            frequency = EXCLUDED.frequency,
+            cron_expression = EXCLUDED.cron_expression,
+            enabled = EXCLUDED.enabled,
+            starts_at = EXCLUDED.starts_at,
+            next_run_at = EXCLUDED.next_run_at,
+            updated_at = NOW()
+        RETURNING *
+        "#
+    )
+    .bind(user_id)
+    .bind(frequency)
+    .bind(cron_expression)
+    .bind(enabled)
+    .bind(starts_at)
+    .bind(next_run_at)
+    .fetch_one(pool)
+    .await?;
+    Ok(map_schedule(row))
+}
+
+pub async fn delete_schedule(pool: &PgPool, user_id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM scan_schedules WHERE user_id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// Locks the most overdue enabled schedule for the rest of the caller's transaction.
+/// Concurrent schedulers skip rows that are already locked.
+pub async fn lock_due_schedule(conn: &mut PgConnection) -> Result<Option<ScanSchedule>, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        SELECT * FROM scan_schedules
+        WHERE enabled AND next_run_at <= NOW()
+        ORDER BY next_run_at
+        LIMIT 1
+        FOR UPDATE SKIP LOCKED
+        "#
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(row.map(map_schedule))
+}
+
+/// Moves a schedule on to its next run, recording the scan it just started, if any.
+pub async fn advance_schedule(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    next_run_at: DateTime<Utc>,
+    scan_id: Option<Uuid>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE scan_schedules
+        SET next_run_at = $2,
+            last_run_at = CASE WHEN $3::uuid IS NULL THEN last_run_at ELSE NOW() END,
+            last_scan_id = COALESCE($3, last_scan_id),
+            updated_at = NOW()
+        WHERE user_id = $1
+        "#
+    )
+    .bind(user_id)
+    .bind(next_run_at)
+    .bind(scan_id)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Turns off a schedule that can't run again, e.g. a cron expression with no future dates.
+pub async fn disable_schedule(conn: &mut PgConnection, user_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE scan_schedules SET enabled = FALSE, updated_at = NOW() WHERE user_id = $1")
+        .bind(user_id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,57 @@
+// src/db/score_repo.rs
+
+use crate::{findings::score::PrivacyScore, models::score::ScanScore};
+use sqlx::{postgres::PgRow, types::Json, PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+fn map_score(row: PgRow) -> ScanScore {
+    ScanScore {
+        scan_id: row.get("scan_id"),
+        user_id: row.get("user_id"),
+        score: row.get("score"),
+        breakdown: row.get::<Json<_>, _>("breakdown").0,
+        computed_at: row.get("computed_at"),
+    }
+}
+
+/// Stores a scan's score, replacing any earlier one.
+pub async fn save_score(
+    conn: &mut PgConnection,
+    scan_id: Uuid,
+    score: &PrivacyScore,
+) -> Result<ScanScore, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO scan_scores (scan_id, user_id, score, breakdown)
+        SELECT id, user_id, $2, $3 FROM scans WHERE id = $1
+        ON CONFLICT (scan_id) DO UPDATE SET
+            score = EXCLUDED.score,
+            breakdown = EXCLUDED.breakdown,
+            computed_at = NOW()
+        RETURNING *
+        "#
+    )
+    .bind(scan_id)
+    .bind(score.score)
+    .bind(Json(&score.breakdown))
+    .fetch_one(conn)
+    .await?;
+    Ok(map_score(row))
+}
+
+pub async fn get_score(pool: &PgPool, scan_id: Uuid) -> Result<Option<ScanScore>, sqlx::Error> {
+    let row = sqlx::query("SELECT * FROM scan_scores WHERE scan_id = $1")
+        .bind(scan_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(map_score))
+}
+
+/// The user's scores, oldest first.
+pub async fn get_score_history(pool: &PgPool, user_id: Uuid) -> Result<Vec<ScanScore>, sqlx::Error> {
+    let rows = sqlx::query("SELECT * FROM scan_scores WHERE user_id = $1 ORDER BY computed_at, scan_id")
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(map_score).collect())
+}
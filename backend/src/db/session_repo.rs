@@ -0,0 +1,135 @@
+// src/db/session_repo.rs
+
+use crate::models::session::Session;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+fn map_session(row: PgRow) -> Session {
+    Session {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        user_agent: row.get("user_agent"),
+        ip_address: row.get("ip_address"),
+        created_at: row.get("created_at"),
+        last_used_at: row.get("last_used_at"),
+        expires_at: row.get("expires_at"),
+        revoked_at: row.get("revoked_at"),
+    }
+}
+
+pub async fn create_session(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    user_agent: Option<&str>,
+    ip_address: Option<&str>,
+    expires_at: DateTime<Utc>,
+) -> Result<Session, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO sessions (user_id, user_agent, ip_address, expires_at)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#
+    )
+    .bind(user_id)
+    .bind(user_agent)
+    .bind(ip_address)
+    .bind(expires_at)
+    .fetch_one(conn)
+    .await?;
+    Ok(map_session(row))
+}
+
+/// Whether the user's session is live, i.e. neither revoked nor expired. Bumps
+/// its last use, at most once a minute to spare a write on every request.
+pub async fn touch_session(pool: &PgPool, session_id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        WITH live AS (
+            SELECT id, last_used_at FROM sessions
+            WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL AND expires_at > NOW()
+        ), touched AS (
+            UPDATE sessions SET last_used_at = NOW()
+            FROM live
+            WHERE sessions.id = live.id AND live.last_used_at < NOW() - INTERVAL '1 minute'
+        )
+        SELECT EXISTS (SELECT 1 FROM live) AS live
+        "#
+    )
+    .bind(session_id)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(row.get("live"))
+}
+
+/// Records a refresh of the session, which keeps it alive until `expires_at`.
+pub async fn extend_session(
+    conn: &mut PgConnection,
+    session_id: Uuid,
+    expires_at: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE sessions SET last_used_at = NOW(), expires_at = $2 WHERE id = $1")
+        .bind(session_id)
+        .bind(expires_at)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// The user's live sessions, most recently used first.
+pub async fn get_live_sessions(pool: &PgPool, user_id: Uuid) -> Result<Vec<Session>, sqlx::Error> {
+    let rows = sqlx::query(
+        r#"
+        SELECT * FROM sessions
+        WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+        ORDER BY last_used_at DESC, id
+        "#
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(map_session).collect())
+}
+
+/// Revokes the user's session and its refresh tokens. Returns whether it was
+/// still live.
+pub async fn revoke_session(pool: &PgPool, user_id: Uuid, session_id: Uuid) -> Result<bool, sqlx::Error> {
+    let revoked = revoke_sessions(pool, user_id, "id = $2", session_id).await?;
+    Ok(revoked > 0)
+}
+
+/// Revokes every session of the user's except `keep`. Returns how many were
+/// revoked.
+pub async fn revoke_other_sessions(pool: &PgPool, user_id: Uuid, keep: Uuid) -> Result<u64, sqlx::Error> {
+    revoke_sessions(pool, user_id, "id <> $2", keep).await
+}
+
+async fn revoke_sessions(pool: &PgPool, user_id: Uuid, filter: &str, session_id: Uuid) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let revoked: Vec<Uuid> = sqlx::query(&format!(
+        r#"
+        UPDATE sessions SET revoked_at = NOW()
+        WHERE user_id = $1 AND {} AND revoked_at IS NULL AND expires_at > NOW()
+        RETURNING id
+        "#,
+        filter
+    ))
+    .bind(user_id)
+    .bind(session_id)
+    .fetch_all(&mut *tx)
+    .await?
+    .iter()
+    .map(|row| row.get("id"))
+    .collect();
+
+    sqlx::query("UPDATE refresh_tokens SET revoked_at = NOW() WHERE session_id = ANY($1) AND revoked_at IS NULL")
+        .bind(&revoked)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(revoked.len() as u64)
+}
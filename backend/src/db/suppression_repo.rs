@@ -0,0 +1,150 @@
+// src/db/suppression_repo.rs
+
+use crate::models::suppression::{PrecisionStat, Suppression};
+use sqlx::{postgres::PgRow, PgPool, Row};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+fn map_suppression(row: PgRow) -> Suppression {
+    Suppression {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        fingerprint: row.get("fingerprint"),
+        source: row.get("source"),
+        finding_id: row.get("finding_id"),
+        feedback_id: row.get("feedback_id"),
+        ignored_finding: row.get("ignored_finding"),
+        created_at: row.get("created_at"),
+    }
+}
+
+fn map_stat(row: PgRow) -> PrecisionStat {
+    let findings: i64 = row.get("findings");
+    let false_positives: i64 = row.get("false_positives");
+    PrecisionStat {
+        key: row.get("key"),
+        findings,
+        false_positives,
+        precision: if findings > 0 { 1.0 - false_positives as f64 / findings as f64 } else { 1.0 },
+    }
+}
+
+/// Suppresses a fingerprint for the user. Reporting an already suppressed
+/// fingerprint again keeps the original suppression.
+pub async fn create_suppression(
+    pool: &PgPool,
+    user_id: Uuid,
+    fingerprint: &str,
+    source: &str,
+    finding_id: Option<Uuid>,
+    feedback_id: Option<Uuid>,
+) -> Result<Suppression, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO suppressions (user_id, fingerprint, source, finding_id, feedback_id)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (user_id, fingerprint) DO UPDATE SET fingerprint = suppressions.fingerprint
+        RETURNING *
+        "#
+    )
+    .bind(user_id)
+    .bind(fingerprint)
+    .bind(source)
+    .bind(finding_id)
+    .bind(feedback_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(map_suppression(row))
+}
+
+/// Records that the suppression moved its finding to ignored.
+pub async fn mark_ignored_finding(pool: &PgPool, suppression_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE suppressions SET ignored_finding = TRUE WHERE id = $1")
+        .bind(suppression_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn get_suppressions_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Suppression>, sqlx::Error> {
+    let rows = sqlx::query("SELECT * FROM suppressions WHERE user_id = $1 ORDER BY created_at DESC, id")
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(map_suppression).collect())
+}
+
+/// Removes one of the user's suppressions, returning it if it existed.
+pub async fn delete_suppression(
+    pool: &PgPool,
+    user_id: Uuid,
+    suppression_id: Uuid,
+) -> Result<Option<Suppression>, sqlx::Error> {
+    let row = sqlx::query("DELETE FROM suppressions WHERE id = $1 AND user_id = $2 RETURNING *")
+        .bind(suppression_id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(map_suppression))
+}
+
+/// Fingerprints suppressed by the owner of the scan.
+pub async fn get_suppressed_fingerprints(pool: &PgPool, scan_id: Uuid) -> Result<HashSet<String>, sqlx::Error> {
+    let rows = sqlx::query(
+        r#"
+        SELECT suppressions.fingerprint FROM suppressions
+        JOIN scans ON scans.user_id = suppressions.user_id
+        WHERE scans.id = $1
+        "#
+    )
+    .bind(scan_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(|row| row.get("fingerprint")).collect())
+}
+
+/// Share of the user's tracked findings per source that were not reported as false
+/// positives.
+pub async fn get_source_precision(pool: &PgPool, user_id: Uuid) -> Result<Vec<PrecisionStat>, sqlx::Error> {
+    let rows = sqlx::query(
+        r#"
+        SELECT findings.source AS key,
+               COUNT(*) AS findings,
+               COUNT(suppressions.id) AS false_positives
+        FROM findings
+        LEFT JOIN suppressions
+            ON suppressions.user_id = findings.user_id AND suppressions.fingerprint = findings.fingerprint
+        WHERE findings.user_id = $1
+        GROUP BY findings.source
+        ORDER BY findings.source
+        "#
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(map_stat).collect())
+}
+
+/// Share of the user's tracked findings per risk rule that were not reported as
+/// false positives.
+pub async fn get_rule_precision(pool: &PgPool, user_id: Uuid) -> Result<Vec<PrecisionStat>, sqlx::Error> {
+    let rows = sqlx::query(
+        r#"
+        SELECT rule AS key,
+               COUNT(*) AS findings,
+               COUNT(suppressions.id) AS false_positives
+        FROM findings
+        CROSS JOIN LATERAL unnest(findings.risk_rules) AS rule
+        LEFT JOIN suppressions
+            ON suppressions.user_id = findings.user_id AND suppressions.fingerprint = findings.fingerprint
+        WHERE findings.user_id = $1
+        GROUP BY rule
+        ORDER BY rule
+        "#
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(map_stat).collect())
+}
@@ -0,0 +1,343 @@
+// src/db/takedown_repo.rs
+
+use crate::{
+    models::takedown::{
+        RemovalCheck, RemovalCheckOutcome, TakedownChannel, TakedownEscalation, TakedownRequest,
+        TakedownResponse, TakedownResponseKind, TakedownStatus,
+    },
+    takedown::TakedownLetter,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+/// A request about to be recorded as sent. The removal is first checked at the
+/// deadline.
+pub struct NewTakedownRequest<'a> {
+    pub user_id: Uuid,
+    pub finding_id: Uuid,
+    pub broker: &'a str,
+    pub jurisdiction: &'a str,
+    pub channel: TakedownChannel,
+    pub subject: &'a str,
+    pub body: &'a str,
+    pub sent_at: DateTime<Utc>,
+    pub deadline_at: DateTime<Utc>,
+    pub email_id: Option<Uuid>,
+}
+
+fn map_request(row: PgRow) -> TakedownRequest {
+    TakedownRequest {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        finding_id: row.get("finding_id"),
+        broker: row.get("broker"),
+        jurisdiction: row.get("jurisdiction"),
+        channel: row.get("channel"),
+        status: row.get("status"),
+        subject: row.get("subject"),
+        body: row.get("body"),
+        sent_at: row.get("sent_at"),
+        extension_days: row.get("extension_days"),
+        deadline_at: row.get("deadline_at"),
+        overdue_at: row.get("overdue_at"),
+        email_id: row.get("email_id"),
+        next_check_at: row.get("next_check_at"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+fn map_response(row: PgRow) -> TakedownResponse {
+    TakedownResponse {
+        id: row.get("id"),
+        request_id: row.get("request_id"),
+        kind: row.get("kind"),
+        note: row.get("note"),
+        received_at: row.get("received_at"),
+        created_at: row.get("created_at"),
+    }
+}
+
+fn map_check(row: PgRow) -> RemovalCheck {
+    RemovalCheck {
+        id: row.get("id"),
+        request_id: row.get("request_id"),
+        finding_id: row.get("finding_id"),
+        outcome: row.get("outcome"),
+        evidence: row.get("evidence"),
+        checked_at: row.get("checked_at"),
+    }
+}
+
+fn map_escalation(row: PgRow) -> TakedownEscalation {
+    TakedownEscalation {
+        id: row.get("id"),
+        request_id: row.get("request_id"),
+        template_set: row.get("template_set"),
+        subject: row.get("subject"),
+        text_body: row.get("text_body"),
+        html_body: row.get("html_body"),
+        evidence_ids: row.get("evidence_ids"),
+        created_at: row.get("created_at"),
+    }
+}
+
+/// Records a takedown request. Returns None, recording nothing, if the finding
+/// already has an open request.
+pub async fn create_request(
+    conn: &mut PgConnection,
+    request: &NewTakedownRequest<'_>,
+) -> Result<Option<TakedownRequest>, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO takedown_requests (user_id, finding_id, broker, jurisdiction, channel, subject, body, sent_at, deadline_at, email_id, next_check_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $9)
+        ON CONFLICT (finding_id) WHERE status IN ('sent', 'acknowledged', 'extended') DO NOTHING
+        RETURNING *
+        "#
+    )
+    .bind(request.user_id)
+    .bind(request.finding_id)
+    .bind(request.broker)
+    .bind(request.jurisdiction)
+    .bind(request.channel)
+    .bind(request.subject)
+    .bind(request.body)
+    .bind(request.sent_at)
+    .bind(request.deadline_at)
+    .bind(request.email_id)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(map_request))
+}
+
+pub async fn get_requests_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<TakedownRequest>, sqlx::Error> {
+    let rows = sqlx::query("SELECT * FROM takedown_requests WHERE user_id = $1 ORDER BY sent_at DESC, id")
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(map_request).collect())
+}
+
+pub async fn get_request(pool: &PgPool, request_id: Uuid) -> Result<Option<TakedownRequest>, sqlx::Error> {
+    let row = sqlx::query("SELECT * FROM takedown_requests WHERE id = $1")
+        .bind(request_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(map_request))
+}
+
+/// Whether the finding has a request the broker hasn't answered yet.
+pub async fn has_pending_request(pool: &PgPool, finding_id: Uuid) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM takedown_requests
+            WHERE finding_id = $1 AND status IN ('sent', 'acknowledged', 'extended')
+        ) AS pending
+        "#
+    )
+    .bind(finding_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(row.get("pending"))
+}
+
+pub async fn get_responses(pool: &PgPool, request_id: Uuid) -> Result<Vec<TakedownResponse>, sqlx::Error> {
+    let rows = sqlx::query("SELECT * FROM takedown_responses WHERE request_id = $1 ORDER BY received_at, id")
+        .bind(request_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(map_response).collect())
+}
+
+/// Records a response from the broker together with its effect on the request:
+/// the status, extension, deadline and next removal check are saved from
+/// `request`. Returns the
+/// updated request.
+pub async fn record_response(
+    pool: &PgPool,
+    request: &TakedownRequest,
+    kind: TakedownResponseKind,
+    note: Option<&str>,
+    received_at: DateTime<Utc>,
+) -> Result<TakedownRequest, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO takedown_responses (request_id, kind, note, received_at)
+        VALUES ($1, $2, $3, $4)
+        "#
+    )
+    .bind(request.id)
+    .bind(kind)
+    .bind(note)
+    .bind(received_at)
+    .execute(&mut *tx)
+    .await?;
+
+    let request = sqlx::query(
+        r#"
+        UPDATE takedown_requests SET
+            status = $2,
+            extension_days = $3,
+            deadline_at = $4,
+            next_check_at = $5,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#
+    )
+    .bind(request.id)
+    .bind(request.status)
+    .bind(request.extension_days)
+    .bind(request.deadline_at)
+    .bind(request.next_check_at)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(map_request(request))
+}
+
+/// Locks the pending request whose deadline passed longest ago, skipping any
+/// another process is handling.
+pub async fn lock_overdue_request(conn: &mut PgConnection) -> Result<Option<TakedownRequest>, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        SELECT * FROM takedown_requests
+        WHERE status IN ('sent', 'acknowledged', 'extended') AND deadline_at <= NOW()
+        ORDER BY deadline_at
+        LIMIT 1
+        FOR UPDATE SKIP LOCKED
+        "#
+    )
+    .fetch_optional(conn)
+    .await?;
+    Ok(row.map(map_request))
+}
+
+pub async fn mark_overdue(conn: &mut PgConnection, request_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE takedown_requests SET status = 'overdue', overdue_at = NOW(), updated_at = NOW()
+        WHERE id = $1
+        "#
+    )
+    .bind(request_id)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+pub async fn create_escalation(
+    conn: &mut PgConnection,
+    request_id: Uuid,
+    letter: &TakedownLetter,
+    evidence_ids: &[Uuid],
+) -> Result<TakedownEscalation, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO takedown_escalations (request_id, template_set, subject, text_body, html_body, evidence_ids)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#
+    )
+    .bind(request_id)
+    .bind(&letter.template_set)
+    .bind(&letter.subject)
+    .bind(&letter.text)
+    .bind(&letter.html)
+    .bind(evidence_ids)
+    .fetch_one(conn)
+    .await?;
+    Ok(map_escalation(row))
+}
+
+pub async fn get_escalation(pool: &PgPool, request_id: Uuid) -> Result<Option<TakedownEscalation>, sqlx::Error> {
+    let row = sqlx::query("SELECT * FROM takedown_escalations WHERE request_id = $1")
+        .bind(request_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(map_escalation))
+}
+
+/// Claims the request whose removal check is most overdue, pushing its next
+/// check to `lease_until` so other workers skip it and a crashed check is retried.
+pub async fn claim_due_check(
+    pool: &PgPool,
+    lease_until: DateTime<Utc>,
+) -> Result<Option<TakedownRequest>, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        UPDATE takedown_requests SET next_check_at = $1
+        WHERE id = (
+            SELECT id FROM takedown_requests
+            WHERE next_check_at <= NOW()
+            ORDER BY next_check_at
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING *
+        "#
+    )
+    .bind(lease_until)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(map_request))
+}
+
+pub async fn create_removal_check(
+    conn: &mut PgConnection,
+    request: &TakedownRequest,
+    outcome: RemovalCheckOutcome,
+    evidence: &serde_json::Value,
+) -> Result<RemovalCheck, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO removal_checks (request_id, finding_id, outcome, evidence)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#
+    )
+    .bind(request.id)
+    .bind(request.finding_id)
+    .bind(outcome)
+    .bind(evidence)
+    .fetch_one(conn)
+    .await?;
+    Ok(map_check(row))
+}
+
+/// Saves the request's status after a removal check and when to check next; None
+/// stops monitoring.
+pub async fn finish_check(
+    conn: &mut PgConnection,
+    request_id: Uuid,
+    status: TakedownStatus,
+    next_check_at: Option<DateTime<Utc>>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE takedown_requests SET status = $2, next_check_at = $3, updated_at = NOW()
+        WHERE id = $1
+        "#
+    )
+    .bind(request_id)
+    .bind(status)
+    .bind(next_check_at)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_removal_checks(pool: &PgPool, request_id: Uuid) -> Result<Vec<RemovalCheck>, sqlx::Error> {
+    let rows = sqlx::query("SELECT * FROM removal_checks WHERE request_id = $1 ORDER BY checked_at, id")
+        .bind(request_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(map_check).collect())
+}
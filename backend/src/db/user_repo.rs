@@ -1,7 +1,22 @@
 // src/db/user_repo.rs
 
 use crate::models::user::User;
-use sqlx::{PgPool, Row};
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, PgConnection, PgPool, Row};
+use uuid::Uuid;
+
+fn map_user(row: PgRow) -> User {
+    User {
+        id: row.get("id"),
+        username: row.get("username"),
+        email: row.get("email"),
+        password_hash: row.get("password_hash"),
+        email_verified_at: row.get("email_verified_at"),
+        verification_sent_at: row.get("verification_sent_at"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
 
 pub async fn create_user(
     pool: &PgPool,
@@ -18,16 +33,7 @@ pub async fn create_user(
     .fetch_one(pool)
     .await?;
 
-    let user = User {
-        id: row.get("id"),
-        username: row.get("username"),
-        email: row.get("email"),
-        password_hash: row.get("password_hash"),
-        created_at: row.get("created_at"),
-        updated_at: row.get("updated_at"),
-    };
-
-    Ok(user)
+    Ok(map_user(row))
 }
 
 pub async fn find_user_by_email(pool: &PgPool, email: &str) -> Result<Option<User>, sqlx::Error> {
@@ -36,16 +42,7 @@ pub async fn find_user_by_email(pool: &PgPool, email: &str) -> Result<Option<Use
         .fetch_optional(pool)
         .await?;
 
-    let user = row.map(|r| User {
-        id: r.get("id"),
-        username: r.get("username"),
-        email: r.get("email"),
-        password_hash: r.get("password_hash"),
-        created_at: r.get("created_at"),
-        updated_at: r.get("updated_at"),
-    });
-
-    Ok(user)
+    Ok(row.map(map_user))
 }
 
 pub async fn find_user_by_username(
@@ -57,14 +54,57 @@ pub async fn find_user_by_username(
         .fetch_optional(pool)
         .await?;
 
-    let user = row.map(|r| User {
-        id: r.get("id"),
-        username: r.get("username"),
-        email: r.get("email"),
-        password_hash: r.get("password_hash"),
-        created_at: r.get("created_at"),
-        updated_at: r.get("updated_at"),
-    });
+    Ok(row.map(map_user))
+}
 
-    Ok(user)
+pub async fn find_user_by_id(pool: &PgPool, user_id: Uuid) -> Result<Option<User>, sqlx::Error> {
+    let row = sqlx::query("SELECT * FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(row.map(map_user))
+}
+
+/// Claims the right to send the user another verification link: records the
+/// send unless the email is already verified or a link went out after
+/// `sent_before`. Returns whether the link may be sent.
+pub async fn claim_verification_send(
+    pool: &PgPool,
+    user_id: Uuid,
+    sent_before: DateTime<Utc>,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        UPDATE users SET verification_sent_at = NOW()
+        WHERE id = $1 AND email_verified_at IS NULL
+          AND (verification_sent_at IS NULL OR verification_sent_at < $2)
+        "#
+    )
+    .bind(user_id)
+    .bind(sent_before)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Marks the user's email as verified, provided it is still `email`. Returns the
+/// user when it is, whether or not it was verified before.
+pub async fn mark_email_verified(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    email: &str,
+) -> Result<Option<User>, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        UPDATE users SET email_verified_at = COALESCE(email_verified_at, NOW())
+        WHERE id = $1 AND email = $2
+        RETURNING *
+        "#
+    )
+    .bind(user_id)
+    .bind(email)
+    .fetch_optional(conn)
+    .await?;
+    Ok(row.map(map_user))
 }
@@ -0,0 +1,230 @@
+// src/db/verification_repo.rs
+
+use crate::{
+    models::verification::{IdentifierVerification, VerifiedIdentifier},
+    scanner::{Identifier, IdentifierKind},
+};
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, PgPool, Row};
+use uuid::Uuid;
+
+fn map_verification(row: PgRow) -> IdentifierVerification {
+    IdentifierVerification {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        kind: row.get("kind"),
+        value: row.get("value"),
+        code_hash: row.get("code_hash"),
+        attempts: row.get("attempts"),
+        expires_at: row.get("expires_at"),
+        consumed_at: row.get("consumed_at"),
+        created_at: row.get("created_at"),
+    }
+}
+
+fn map_verified(row: PgRow) -> VerifiedIdentifier {
+    VerifiedIdentifier {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        kind: row.get("kind"),
+        value: row.get("value"),
+        verified_at: row.get("verified_at"),
+    }
+}
+
+pub async fn create_verification(
+    conn: &mut sqlx::PgConnection,
+    user_id: Uuid,
+    kind: IdentifierKind,
+    value: &str,
+    code_hash: &str,
+    expires_at: DateTime<Utc>,
+) -> Result<IdentifierVerification, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO identifier_verifications (user_id, kind, value, code_hash, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#
+    )
+    .bind(user_id)
+    .bind(kind)
+    .bind(value)
+    .bind(code_hash)
+    .bind(expires_at)
+    .fetch_one(conn)
+    .await?;
+    Ok(map_verification(row))
+}
+
+/// Holds back other code sends for the user until the transaction ends, by locking
+/// their row in `users`, so the send limits can't be raced past.
+pub async fn lock_sends(conn: &mut sqlx::PgConnection, user_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT id FROM users WHERE id = $1 FOR UPDATE")
+        .bind(user_id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// When a code was last sent for this identifier, used to throttle resends.
+pub async fn last_sent_at(
+    conn: &mut sqlx::PgConnection,
+    user_id: Uuid,
+    kind: IdentifierKind,
+    value: &str,
+) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        SELECT MAX(created_at) AS last_sent_at FROM identifier_verifications
+        WHERE user_id = $1 AND kind = $2 AND value = $3
+        "#
+    )
+    .bind(user_id)
+    .bind(kind)
+    .bind(value)
+    .fetch_one(conn)
+    .await?;
+    Ok(row.get("last_sent_at"))
+}
+
+/// How many codes the user has been sent since `since`, for any identifier.
+pub async fn count_sent_since(
+    conn: &mut sqlx::PgConnection,
+    user_id: Uuid,
+    since: DateTime<Utc>,
+) -> Result<i64, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT COUNT(*) AS sent FROM identifier_verifications WHERE user_id = $1 AND created_at > $2"
+    )
+    .bind(user_id)
+    .bind(since)
+    .fetch_one(conn)
+    .await?;
+    Ok(row.get("sent"))
+}
+
+pub async fn find_verification(
+    pool: &PgPool,
+    verification_id: Uuid,
+) -> Result<Option<IdentifierVerification>, sqlx::Error> {
+    let row = sqlx::query("SELECT * FROM identifier_verifications WHERE id = $1")
+        .bind(verification_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(map_verification))
+}
+
+/// Counts an attempt at the verification's code before it is compared, so parallel
+/// guesses can't get past the limit. Returns false, counting nothing, once
+/// `max_attempts` have been made.
+pub async fn claim_attempt(pool: &PgPool, verification_id: Uuid, max_attempts: i32) -> Result<bool, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        UPDATE identifier_verifications SET attempts = attempts + 1
+        WHERE id = $1 AND attempts < $2
+        RETURNING attempts
+        "#
+    )
+    .bind(verification_id)
+    .bind(max_attempts)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.is_some())
+}
+
+/// Consumes the verification and records its identifier as verified for the user.
+/// Returns None if the verification was already consumed.
+pub async fn confirm_verification(
+    pool: &PgPool,
+    verification_id: Uuid,
+) -> Result<Option<VerifiedIdentifier>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let row = sqlx::query(
+        r#"
+        UPDATE identifier_verifications SET consumed_at = NOW()
+        WHERE id = $1 AND consumed_at IS NULL
+        RETURNING user_id, kind, value
+        "#
+    )
+    .bind(verification_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    let user_id: Uuid = row.get("user_id");
+    let kind: IdentifierKind = row.get("kind");
+    let value: String = row.get("value");
+    let verified = mark_verified(&mut tx, user_id, kind, &value).await?;
+
+    tx.commit().await?;
+    Ok(Some(verified))
+}
+
+/// Records an identifier as verified, e.g. when ownership was proven another way.
+pub async fn mark_verified(
+    conn: &mut sqlx::PgConnection,
+    user_id: Uuid,
+    kind: IdentifierKind,
+    value: &str,
+) -> Result<VerifiedIdentifier, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        INSERT INTO verified_identifiers (user_id, kind, value)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id, kind, value) DO UPDATE SET verified_at = verified_identifiers.verified_at
+        RETURNING *
+        "#
+    )
+    .bind(user_id)
+    .bind(kind)
+    .bind(value)
+    .fetch_one(conn)
+    .await?;
+    Ok(map_verified(row))
+}
+
+pub async fn get_verified_identifiers(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<VerifiedIdentifier>, sqlx::Error> {
+    let rows = sqlx::query("SELECT * FROM verified_identifiers WHERE user_id = $1 ORDER BY verified_at")
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+    Ok(rows.into_iter().map(map_verified).collect())
+}
+
+/// The emails and phone numbers among `identifiers` that the user has not verified.
+pub async fn find_unverified<'a>(
+    pool: &PgPool,
+    user_id: Uuid,
+    identifiers: &'a [Identifier],
+) -> Result<Vec<&'a Identifier>, sqlx::Error> {
+    let verified = get_verified_identifiers(pool, user_id).await?;
+    Ok(identifiers
+        .iter()
+        .filter(|identifier| match identifier.verifiable_value() {
+            Some(value) => !verified
+                .iter()
+                .any(|v| v.kind == identifier.kind() && v.value == value),
+            None => false,
+        })
+        .collect())
+}
+
+pub async fn delete_verified_identifier(
+    pool: &PgPool,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM verified_identifiers WHERE id = $1 AND user_id = $2")
+        .bind(id)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() == 1)
+}
@@ -11,6 +11,9 @@ pub enum AppError {
     // Add specific error types here
     InternalServerError,
     BadRequest(String),
+    NotFound(String),
+    Forbidden(String),
+    TooManyRequests(String),
 }
 
 impl IntoResponse for AppError {
@@ -21,6 +24,9 @@ impl IntoResponse for AppError {
                 "Internal Server Error".to_string(),
             ),
             AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
+            AppError::TooManyRequests(msg) => (StatusCode::TOO_MANY_REQUESTS, msg),
         };
 
         let body = Json(json!({
@@ -0,0 +1,133 @@
+// src/events.rs
+
+// Scan progress notifications. Workers and database triggers publish them with
+// Postgres NOTIFY; each API instance LISTENs and fans them out to its SSE clients.
+
+use crate::models::scan::ScanStatus;
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgListener, PgPool};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+pub const SCAN_EVENTS_CHANNEL: &str = "scan_events";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScanNotification {
+    /// The scan's status column changed (sent by a trigger on `scans`).
+    Status { scan_id: Uuid, status: ScanStatus },
+    /// A source started or finished.
+    Source {
+        scan_id: Uuid,
+        source: String,
+        state: SourceState,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        findings: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// A result row was inserted (sent by a trigger on `scan_results`).
+    Result { scan_id: Uuid, result_id: Uuid },
+    /// Not from Postgres: the listener (re)connected, so notifications sent while it
+    /// was down were lost and subscribers should re-read what they follow.
+    #[serde(skip)]
+    Resync,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceState {
+    Started,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl ScanNotification {
+    /// The scan the notification is about; `None` for `Resync`, which is about all of them.
+    pub fn scan_id(&self) -> Option<Uuid> {
+        match self {
+            ScanNotification::Status { scan_id, .. }
+            | ScanNotification::Source { scan_id, .. }
+            | ScanNotification::Result { scan_id, .. } => Some(*scan_id),
+            ScanNotification::Resync => None,
+        }
+    }
+}
+
+pub async fn publish(pool: &PgPool, notification: &ScanNotification) -> Result<(), sqlx::Error> {
+    let payload = serde_json::to_string(notification).expect("notification serializes");
+    sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(SCAN_EVENTS_CHANNEL)
+        .bind(payload)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// In-process fan-out of notifications received from Postgres.
+#[derive(Clone)]
+pub struct ScanEventHub {
+    sender: broadcast::Sender<ScanNotification>,
+}
+
+impl Default for ScanEventHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScanEventHub {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(1024);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ScanNotification> {
+        self.sender.subscribe()
+    }
+
+    /// Listens on the scan events channel for the lifetime of the process and
+    /// forwards every notification to subscribers. Sends `Resync` each time it
+    /// starts listening, since anything published before then was missed.
+    pub fn spawn_listener(&self, pool: PgPool) {
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            loop {
+                let mut listener = match PgListener::connect_with(&pool).await {
+                    Ok(listener) => listener,
+                    Err(e) => {
+                        tracing::error!("Failed to connect scan events listener: {}", e);
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                        continue;
+                    }
+                };
+                if let Err(e) = listener.listen(SCAN_EVENTS_CHANNEL).await {
+                    tracing::error!("Failed to listen for scan events: {}", e);
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    continue;
+                }
+                let _ = sender.send(ScanNotification::Resync);
+
+                loop {
+                    match listener.recv().await {
+                        Ok(notification) => {
+                            match serde_json::from_str::<ScanNotification>(notification.payload()) {
+                                // Sending only fails when nobody is subscribed
+                                Ok(event) => {
+                                    let _ = sender.send(event);
+                                }
+                                Err(e) => tracing::warn!("Ignoring malformed scan event: {}", e),
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("Scan events listener failed, reconnecting: {}", e);
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,59 @@
+// src/evidence/fs.rs
+
+use crate::evidence::{is_hash, sha256_hex, BlobStore, EvidenceError};
+use async_trait::async_trait;
+use std::{io::ErrorKind, path::PathBuf};
+use uuid::Uuid;
+
+/// Blob store in a local directory, one file per blob under a subdirectory named
+/// after the first two hex digits of its hash.
+pub struct FsBlobStore {
+    root: PathBuf,
+}
+
+impl FsBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, hash: &str) -> PathBuf {
+        self.root.join(&hash[..2]).join(hash)
+    }
+}
+
+#[async_trait]
+impl BlobStore for FsBlobStore {
+    async fn put(&self, data: &[u8]) -> Result<String, EvidenceError> {
+        let hash = sha256_hex(data);
+        let path = self.path(&hash);
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(hash);
+        }
+
+        let dir = path.parent().expect("blob paths have a parent");
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| EvidenceError(format!("failed to create {}: {}", dir.display(), e)))?;
+        // Written aside and renamed into place, so a blob is never seen half-written
+        let partial = dir.join(format!(".{}.{}", hash, Uuid::new_v4().simple()));
+        tokio::fs::write(&partial, data)
+            .await
+            .map_err(|e| EvidenceError(format!("failed to write {}: {}", partial.display(), e)))?;
+        tokio::fs::rename(&partial, &path)
+            .await
+            .map_err(|e| EvidenceError(format!("failed to store {}: {}", path.display(), e)))?;
+        Ok(hash)
+    }
+
+    async fn get(&self, hash: &str) -> Result<Option<Vec<u8>>, EvidenceError> {
+        if !is_hash(hash) {
+            return Ok(None);
+        }
+        let path = self.path(hash);
+        match tokio::fs::read(&path).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(EvidenceError(format!("failed to read {}: {}", path.display(), e))),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+// src/evidence/mod.rs
+
+// Evidence of what a source showed when a finding was made. The raw response is
+// kept in a content-addressed blob store, keyed by its SHA-256, and each finding's
+// snapshots form a hash chain, so a snapshot altered or removed after the fact
+// shows up when the chain is verified.
+
+pub mod fs;
+
+use crate::{db::evidence_repo, models::evidence::EvidenceSnapshot};
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::{env, fmt, sync::Arc};
+use uuid::Uuid;
+
+pub const DEFAULT_EVIDENCE_DIR: &str = "evidence";
+
+/// A response captured by a scanner, before it is stored.
+#[derive(Debug, Clone)]
+pub struct Capture {
+    pub url: String,
+    pub status: u16,
+    /// Response headers in the order received.
+    pub headers: Vec<(String, String)>,
+    pub content_type: Option<String>,
+    /// Shared by every finding made from the same response.
+    pub body: Arc<[u8]>,
+    pub captured_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct EvidenceError(pub String);
+
+impl fmt::Display for EvidenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for EvidenceError {}
+
+/// Storage for captured bodies, addressed by the hex SHA-256 of their contents.
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Stores `data` unless it is already there and returns its hash.
+    async fn put(&self, data: &[u8]) -> Result<String, EvidenceError>;
+
+    /// The blob with hash `hash`, if stored.
+    async fn get(&self, hash: &str) -> Result<Option<Vec<u8>>, EvidenceError>;
+}
+
+/// Blob store configured by `EVIDENCE_DIR` (default `evidence`), a directory on
+/// the local filesystem shared by the API server and the workers.
+pub fn blob_store_from_env() -> Arc<dyn BlobStore> {
+    let dir = env::var("EVIDENCE_DIR").unwrap_or_else(|_| DEFAULT_EVIDENCE_DIR.into());
+    Arc::new(fs::FsBlobStore::new(dir))
+}
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// Whether `hash` looks like a hex SHA-256, as blob keys are.
+pub fn is_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.bytes().all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// The fields a snapshot's chain hash covers.
+pub struct ChainedFields<'a> {
+    pub finding_id: Uuid,
+    pub position: i32,
+    pub url: &'a str,
+    pub http_status: i16,
+    pub headers: &'a serde_json::Value,
+    pub content_type: Option<&'a str>,
+    pub body_hash: &'a str,
+    pub body_size: i64,
+    pub captured_at: DateTime<Utc>,
+    pub prev_hash: Option<&'a str>,
+}
+
+/// SHA-256 over the snapshot's fields and the previous snapshot's chain hash, hex
+/// encoded.
+pub fn chain_hash(fields: &ChainedFields<'_>) -> String {
+    // serde_json keeps object keys sorted, so the encoding is stable
+    let canonical = json!({
+        "finding_id": fields.finding_id,
+        "position": fields.position,
+        "url": fields.url,
+        "http_status": fields.http_status,
+        "headers": fields.headers,
+        "content_type": fields.content_type,
+        "body_hash": fields.body_hash,
+        "body_size": fields.body_size,
+        "captured_at": fields.captured_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+        "prev_hash": fields.prev_hash,
+    });
+    sha256_hex(canonical.to_string().as_bytes())
+}
+
+/// A snapshot as checked against the chain and the blob store.
+#[derive(Debug, Serialize)]
+pub struct VerifiedSnapshot {
+    #[serde(flatten)]
+    pub snapshot: EvidenceSnapshot,
+    pub verified: bool,
+    /// Why the snapshot failed verification.
+    pub problem: Option<String>,
+}
+
+/// Checks a finding's snapshots, in chain order: each must follow on from the one
+/// before, hash to its recorded chain hash, and have its body intact in `store`.
+pub async fn verify_chain(store: &dyn BlobStore, snapshots: Vec<EvidenceSnapshot>) -> Vec<VerifiedSnapshot> {
+    let mut verified: Vec<VerifiedSnapshot> = Vec::with_capacity(snapshots.len());
+    for snapshot in snapshots {
+        let previous = verified.last().map(|previous| &previous.snapshot);
+        let problem = check_snapshot(store, &snapshot, previous).await;
+        verified.push(VerifiedSnapshot { snapshot, verified: problem.is_none(), problem });
+    }
+    verified
+}
+
+async fn check_snapshot(
+    store: &dyn BlobStore,
+    snapshot: &EvidenceSnapshot,
+    previous: Option<&EvidenceSnapshot>,
+) -> Option<String> {
+    let position = previous.map_or(1, |previous| previous.position + 1);
+    let prev_hash = previous.map(|previous| previous.chain_hash.as_str());
+    if snapshot.position != position || snapshot.prev_hash.as_deref() != prev_hash {
+        return Some("Does not follow on from the previous snapshot".to_string());
+    }
+    if chain_hash(&snapshot.chained_fields()) != snapshot.chain_hash {
+        return Some("Recorded details do not match the chain hash".to_string());
+    }
+    match store.get(&snapshot.body_hash).await {
+        Ok(Some(body)) if sha256_hex(&body) == snapshot.body_hash => None,
+        Ok(Some(_)) => Some("Stored body does not match its hash".to_string()),
+        Ok(None) => Some("Body is missing from the evidence store".to_string()),
+        Err(e) => Some(e.to_string()),
+    }
+}
+
+/// Stores the captured body and appends a snapshot of it to the finding's chain.
+pub async fn record_capture(
+    pool: &PgPool,
+    store: &dyn BlobStore,
+    finding_id: Uuid,
+    scan_id: Option<Uuid>,
+    capture: &Capture,
+) -> Result<EvidenceSnapshot, EvidenceError> {
+    let body_hash = store.put(&capture.body).await?;
+    evidence_repo::append_snapshot(pool, finding_id, scan_id, capture, &body_hash)
+        .await
+        .map_err(|e| EvidenceError(format!("failed to record snapshot: {}", e)))
+}
@@ -0,0 +1,74 @@
+// src/findings/diff.rs
+
+use crate::models::scan::ScanResult;
+use serde::Serialize;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// How a scan's findings compare to an earlier scan's, matched by fingerprint.
+#[derive(Debug, Serialize)]
+pub struct ScanDiff {
+    pub scan_id: Uuid,
+    pub against_scan_id: Uuid,
+    pub summary: ChangeSummary,
+    /// Found now but not before.
+    pub new: Vec<ScanResult>,
+    /// Found both times; the current scan's result is listed.
+    pub persisting: Vec<ScanResult>,
+    /// Found before but not now; the earlier scan's result is listed.
+    pub resolved: Vec<ScanResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangeSummary {
+    pub against_scan_id: Uuid,
+    pub new: usize,
+    pub persisting: usize,
+    pub resolved: usize,
+}
+
+impl ScanDiff {
+    /// Compares `current`, the results of `scan_id`, with `previous`, the results of
+    /// `against_scan_id`. Results sharing a fingerprint within a scan count once.
+    pub fn between(
+        scan_id: Uuid,
+        current: &[ScanResult],
+        against_scan_id: Uuid,
+        previous: &[ScanResult],
+    ) -> Self {
+        let before: HashSet<&str> = previous.iter().map(|r| r.fingerprint.as_str()).collect();
+        let now: HashSet<&str> = current.iter().map(|r| r.fingerprint.as_str()).collect();
+
+        let mut new = Vec::new();
+        let mut persisting = Vec::new();
+        let mut seen = HashSet::new();
+        for result in current.iter().filter(|r| seen.insert(r.fingerprint.as_str())) {
+            if before.contains(result.fingerprint.as_str()) {
+                persisting.push(result.clone());
+            } else {
+                new.push(result.clone());
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let resolved: Vec<ScanResult> = previous
+            .iter()
+            .filter(|r| !now.contains(r.fingerprint.as_str()) && seen.insert(r.fingerprint.as_str()))
+            .cloned()
+            .collect();
+
+        ScanDiff {
+            scan_id,
+            against_scan_id,
+            summary: ChangeSummary {
+                against_scan_id,
+                new: new.len(),
+                persisting: persisting.len(),
+                resolved: resolved.len(),
+            },
+            new,
+            persisting,
+            resolved,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// src/findings/fingerprint.rs
+
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+
+/// Detail fields left out of fingerprints. Most describe the source rather than the
+/// finding, so editing a broker's catalog entry, say its opt-out URL, doesn't make
+/// every finding from it look new. `searched` is the identifier that led to the
+/// finding: a listing found by both an email and a name search is one finding.
+/// `match` explains how the record was matched to the user's profile, which can
+/// change as the profile does.
+const IGNORED_KEYS: &[&str] = &[
+    "source",
+    "broker_name",
+    "opt_out_url",
+    "jurisdiction",
+    "contact_email",
+    "searched",
+    "match",
+];
+
+/// Record fields that identify a broker listing the broker doesn't link to. The
+/// rest of a record, like the age or the phone numbers shown, changes while the
+/// listing stays the same.
+const LISTING_KEY_FIELDS: &[&str] = &["name", "home_address"];
+
+/// Record fields left out when a listing has nothing better to be identified by.
+const VOLATILE_RECORD_FIELDS: &[&str] = &["age"];
+
+/// Stable identity of a finding across scans: a SHA-256 over the source name and
+/// the finding's `identity`, hex encoded.
+pub fn fingerprint(source: &str, details: &Value) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(source.as_bytes());
+    hasher.update([0]);
+    hasher.update(identity(details).to_string().as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// The normalized part of a finding's details that identifies it. A broker finding
+/// is its listing: the broker plus the listing's URL when the broker links to it,
+/// else the name and address on it. Other findings are their details, less
+/// `IGNORED_KEYS`. Normalizing ignores key order, case, surrounding whitespace and
+/// runs of inner whitespace.
+pub fn identity(details: &Value) -> Value {
+    let Some(record) = details.get("record").and_then(Value::as_object) else {
+        return normalize(details, true);
+    };
+
+    let listing = match details.get("listing_url").filter(|url| !url.is_null()) {
+        Some(url) => json!({ "listing_url": url }),
+        None => {
+            let key = record_fields(record, |field| LISTING_KEY_FIELDS.contains(&field));
+            if key.is_empty() {
+                Value::Object(record_fields(record, |field| !VOLATILE_RECORD_FIELDS.contains(&field)))
+            } else {
+                Value::Object(key)
+            }
+        }
+    };
+    normalize(&json!({ "broker": details.get("broker"), "listing": listing }), false)
+}
+
+fn record_fields(record: &Map<String, Value>, keep: impl Fn(&str) -> bool) -> Map<String, Value> {
+    record
+        .iter()
+        .filter(|(field, value)| keep(field.as_str()) && !value.is_null())
+        .map(|(field, value)| (field.clone(), value.clone()))
+        .collect()
+}
+
+fn normalize(value: &Value, top_level: bool) -> Value {
+    match value {
+        Value::String(s) => Value::String(s.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()),
+        Value::Array(items) => Value::Array(items.iter().map(|item| normalize(item, false)).collect()),
+        // serde_json keeps object keys sorted, so equal objects serialize identically
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(key, _)| !(top_level && IGNORED_KEYS.contains(&key.as_str())))
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key.clone(), normalize(value, false)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
@@ -0,0 +1,5 @@
+// src/findings/mod.rs
+
+pub mod diff;
+pub mod fingerprint;
+pub mod score;
@@ -0,0 +1,152 @@
+// src/findings/score.rs
+
+use crate::models::scan::{FindingType, RiskLevel, ScanResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// Kinds of personal data a finding can expose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataCategory {
+    Password,
+    HomeAddress,
+    Phone,
+    DateOfBirth,
+    Relatives,
+    Email,
+    SocialProfile,
+}
+
+impl DataCategory {
+    /// Points taken off the score when any finding exposes this category. Counted
+    /// once per scan: a home address on five sites is already public.
+    fn penalty(self) -> i32 {
+        match self {
+            DataCategory::Password => 20,
+            DataCategory::HomeAddress => 15,
+            DataCategory::Phone => 10,
+            DataCategory::DateOfBirth => 8,
+            DataCategory::Relatives => 6,
+            DataCategory::Email => 4,
+            DataCategory::SocialProfile => 3,
+        }
+    }
+}
+
+/// Points taken off per distinct finding at a risk level, and the most that level
+/// can take off in total.
+fn risk_penalty(risk_level: RiskLevel) -> (i32, i32) {
+    match risk_level {
+        RiskLevel::Low => (1, 10),
+        RiskLevel::Medium => (3, 20),
+        RiskLevel::High => (6, 30),
+        RiskLevel::Critical => (10, 40),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FactorKind {
+    /// Findings at one risk level.
+    Risk,
+    /// A category of personal data that was exposed.
+    Category,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreFactor {
+    pub kind: FactorKind,
+    /// Risk level or data category name, e.g. `high` or `home_address`.
+    pub name: String,
+    /// Distinct findings contributing to this factor.
+    pub count: usize,
+    pub penalty: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacyScore {
+    /// 100 when nothing was found, falling towards 0 as more is exposed.
+    pub score: i32,
+    pub breakdown: Vec<ScoreFactor>,
+}
+
+/// Data categories a result exposes. Broker records are classified by the names
+/// of their non-empty fields, which are the field names the broker catalog
+/// extracts and `config/risk_rules.toml` refers to. Other fields count for nothing.
+pub fn categories(result: &ScanResult) -> Vec<DataCategory> {
+    match result.finding_type {
+        FindingType::EmailLeak => vec![DataCategory::Email],
+        FindingType::PasswordLeak => vec![DataCategory::Password, DataCategory::Email],
+        FindingType::SocialMedia => vec![DataCategory::SocialProfile],
+        FindingType::DataBroker => {
+            let Some(record) = result.details.get("record").and_then(|r| r.as_object()) else {
+                return Vec::new();
+            };
+            let mut found = HashSet::new();
+            for (field, value) in record {
+                let empty = value.is_null()
+                    || value.as_str().is_some_and(str::is_empty)
+                    || value.as_array().is_some_and(Vec::is_empty);
+                if empty {
+                    continue;
+                }
+                let category = match field.as_str() {
+                    "home_address" => DataCategory::HomeAddress,
+                    "phone" => DataCategory::Phone,
+                    "age" | "dob" | "date_of_birth" => DataCategory::DateOfBirth,
+                    "relatives" => DataCategory::Relatives,
+                    "email" => DataCategory::Email,
+                    _ => continue,
+                };
+                found.insert(category);
+            }
+            found.into_iter().collect()
+        }
+    }
+}
+
+/// Scores a scan's results. Results sharing a fingerprint count as one finding.
+pub fn score_results(results: &[ScanResult]) -> PrivacyScore {
+    let mut seen = HashSet::new();
+    let mut by_risk: BTreeMap<RiskLevel, usize> = BTreeMap::new();
+    let mut by_category: BTreeMap<DataCategory, usize> = BTreeMap::new();
+    for result in results.iter().filter(|r| seen.insert(r.fingerprint.as_str())) {
+        *by_risk.entry(result.risk_level).or_default() += 1;
+        for category in categories(result) {
+            *by_category.entry(category).or_default() += 1;
+        }
+    }
+
+    let mut breakdown = Vec::new();
+    for (risk_level, count) in by_risk.into_iter().rev() {
+        let (each, cap) = risk_penalty(risk_level);
+        breakdown.push(ScoreFactor {
+            kind: FactorKind::Risk,
+            name: enum_name(&risk_level),
+            count,
+            penalty: (each * count as i32).min(cap),
+        });
+    }
+    for (category, count) in by_category {
+        breakdown.push(ScoreFactor {
+            kind: FactorKind::Category,
+            name: enum_name(&category),
+            count,
+            penalty: category.penalty(),
+        });
+    }
+
+    let penalty: i32 = breakdown.iter().map(|factor| factor.penalty).sum();
+    PrivacyScore {
+        score: (100 - penalty).max(0),
+        breakdown,
+    }
+}
+
+/// The snake_case name an enum serializes to.
+fn enum_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
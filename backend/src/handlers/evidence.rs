@@ -0,0 +1,86 @@
+// src/handlers/evidence.rs
+
+use crate::{
+    app_state::AppState,
+    db::evidence_repo,
+    errors::AppError,
+    evidence::{self, VerifiedSnapshot},
+    handlers::finding::get_own_finding,
+};
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Extension, Json,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Serialize)]
+pub struct FindingEvidence {
+    pub finding_id: Uuid,
+    /// Whether every snapshot verified, so the chain is intact.
+    pub chain_valid: bool,
+    /// Oldest first.
+    pub snapshots: Vec<VerifiedSnapshot>,
+}
+
+/// The responses captured as evidence of a finding, each checked against the hash
+/// chain and the blob store.
+pub async fn list_evidence(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Path(finding_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<FindingEvidence>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+    let finding = get_own_finding(&state, user_id, finding_id).await?;
+
+    let snapshots = evidence_repo::get_snapshots(&state.db_pool, finding.id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    let snapshots = evidence::verify_chain(state.blob_store.as_ref(), snapshots).await;
+    Ok((
+        StatusCode::OK,
+        Json(FindingEvidence {
+            finding_id: finding.id,
+            chain_valid: snapshots.iter().all(|snapshot| snapshot.verified),
+            snapshots,
+        }),
+    ))
+}
+
+/// The captured body of a snapshot, exactly as the source returned it. Served as a
+/// download so captured pages never render on this origin.
+pub async fn get_evidence_body(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Path((finding_id, snapshot_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+    let finding = get_own_finding(&state, user_id, finding_id).await?;
+
+    let snapshot = evidence_repo::get_snapshot(&state.db_pool, snapshot_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .filter(|snapshot| snapshot.finding_id == finding.id)
+        .ok_or_else(|| AppError::NotFound("Snapshot not found".to_string()))?;
+    let body = state
+        .blob_store
+        .get(&snapshot.body_hash)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read evidence {}: {}", snapshot.id, e);
+            AppError::InternalServerError
+        })?
+        .ok_or_else(|| AppError::NotFound("Snapshot body is missing from the evidence store".to_string()))?;
+
+    let content_type = snapshot.content_type.unwrap_or_else(|| "application/octet-stream".to_string());
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", snapshot.body_hash)),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        body,
+    ))
+}
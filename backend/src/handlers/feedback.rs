@@ -1,7 +1,12 @@
 // src/handlers/feedback.rs
 
-use crate::{app_state::AppState, db::feedback_repo, errors::AppError};
-use axum::{extract::State, http::StatusCode, Json};
+use crate::{
+    app_state::AppState,
+    db::{feedback_repo, scan_repo},
+    errors::AppError,
+    handlers::suppression,
+};
+use axum::{extract::State, http::StatusCode, Extension, Json};
 use serde::Deserialize;
 use uuid::Uuid;
 
@@ -10,21 +15,41 @@ pub struct FeedbackRequest {
     pub message: String,
     pub is_false_positive: bool,
     pub related_result_id: Option<Uuid>,
-    // user_id could be optional here if we want to allow anonymous feedback
-    // for now, we'll assume it's anonymous if not logged in.
 }
 
+/// Stores feedback, anonymously unless the request carries a token. A logged-in
+/// user reporting one of their own results as a false positive also suppresses
+/// it in future scans.
 pub async fn submit_feedback(
     State(state): State<AppState>,
-    // Optionally extract user_id if the user is logged in
-    // Extension(user_id): Extension<Option<String>>,
+    Extension(user_id): Extension<Option<String>>,
     Json(payload): Json<FeedbackRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
-    // let user_id = user_id.and_then(|id| Uuid::parse_str(&id).ok());
+    let user_id = user_id
+        .map(|id| Uuid::parse_str(&id))
+        .transpose()
+        .map_err(|_| AppError::InternalServerError)?;
 
-    feedback_repo::create_feedback(
+    let reported_result = match (user_id, payload.related_result_id) {
+        (Some(user_id), Some(result_id)) if payload.is_false_positive => {
+            let result = scan_repo::get_scan_result_by_id(&state.db_pool, result_id)
+                .await
+                .map_err(|_| AppError::InternalServerError)?
+                .ok_or_else(|| AppError::NotFound("Result not found".to_string()))?;
+            let scan = scan_repo::get_scan_by_id(&state.db_pool, result.scan_id)
+                .await
+                .map_err(|_| AppError::InternalServerError)?;
+            if scan.map(|scan| scan.user_id) != Some(user_id) {
+                return Err(AppError::NotFound("Result not found".to_string()));
+            }
+            Some((user_id, result))
+        }
+        _ => None,
+    };
+
+    let feedback = feedback_repo::create_feedback(
         &state.db_pool,
-        None, // user_id, // For now, all feedback is anonymous
+        user_id,
         &payload.message,
         payload.is_false_positive,
         payload.related_result_id,
@@ -32,8 +57,11 @@ pub async fn submit_feedback(
     .await
     .map_err(|_| AppError::InternalServerError)?;
 
-    Ok((
-        StatusCode::CREATED,
-        Json(serde_json::json!({ "status": "success" })),
-    ))
+    let mut response = serde_json::json!({ "status": "success" });
+    if let Some((user_id, result)) = reported_result {
+        let suppression = suppression::suppress_result(&state, user_id, &result, feedback.id).await?;
+        response["suppression_id"] = serde_json::json!(suppression.id);
+    }
+
+    Ok((StatusCode::CREATED, Json(response)))
 }
@@ -0,0 +1,123 @@
+// src/handlers/finding.rs
+
+use crate::{
+    app_state::AppState,
+    db::{finding_repo, scan_repo},
+    errors::AppError,
+    models::{
+        finding::{FindingState, FindingStateChange, TrackedFinding},
+        scan::ScanResult,
+    },
+};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct FindingsQuery {
+    pub state: Option<FindingState>,
+}
+
+#[derive(Serialize)]
+pub struct FindingSummary {
+    #[serde(flatten)]
+    pub finding: TrackedFinding,
+    /// Number of scan results that matched this finding.
+    pub occurrences: i64,
+}
+
+#[derive(Serialize)]
+pub struct FindingDetail {
+    #[serde(flatten)]
+    pub finding: TrackedFinding,
+    pub occurrences: Vec<ScanResult>,
+    pub history: Vec<FindingStateChange>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateStateRequest {
+    pub state: FindingState,
+    pub note: Option<String>,
+}
+
+/// The user's exposures, one entry per distinct finding however many scans found it.
+pub async fn list_findings(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Query(query): Query<FindingsQuery>,
+) -> Result<(StatusCode, Json<Vec<FindingSummary>>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let findings = finding_repo::get_findings_by_user(&state.db_pool, user_id, query.state)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .into_iter()
+        .map(|(finding, occurrences)| FindingSummary { finding, occurrences })
+        .collect();
+    Ok((StatusCode::OK, Json(findings)))
+}
+
+/// Loads a finding, making sure it belongs to the user.
+pub(crate) async fn get_own_finding(
+    state: &AppState,
+    user_id: Uuid,
+    finding_id: Uuid,
+) -> Result<TrackedFinding, AppError> {
+    finding_repo::get_finding_by_id(&state.db_pool, finding_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .filter(|finding| finding.user_id == user_id)
+        .ok_or_else(|| AppError::NotFound("Finding not found".to_string()))
+}
+
+pub async fn get_finding(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Path(finding_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<FindingDetail>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let finding = get_own_finding(&state, user_id, finding_id).await?;
+    let occurrences = scan_repo::get_scan_results_by_finding(&state.db_pool, finding.id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    let history = finding_repo::get_state_history(&state.db_pool, finding.id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(FindingDetail { finding, occurrences, history })))
+}
+
+/// Moves a finding to another state, e.g. acknowledging it or marking it resolved.
+pub async fn update_finding_state(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Path(finding_id): Path<Uuid>,
+    Json(payload): Json<UpdateStateRequest>,
+) -> Result<(StatusCode, Json<TrackedFinding>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let finding = get_own_finding(&state, user_id, finding_id).await?;
+    if finding.state == payload.state {
+        return Err(AppError::BadRequest("The finding is already in that state".to_string()));
+    }
+
+    let note = payload.note.as_deref().map(str::trim).filter(|note| !note.is_empty());
+    let updated = finding_repo::set_state(
+        &state.db_pool,
+        finding.id,
+        finding.state,
+        payload.state,
+        note,
+        Some(user_id),
+    )
+    .await
+    .map_err(|_| AppError::InternalServerError)?
+    .ok_or_else(|| AppError::BadRequest("The finding changed state meanwhile, try again".to_string()))?;
+
+    Ok((StatusCode::OK, Json(updated)))
+}
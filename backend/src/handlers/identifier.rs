@@ -0,0 +1,253 @@
+// src/handlers/identifier.rs
+
+use crate::{
+    app_state::AppState,
+    auth::{self, password},
+    db::{outbox_repo, verification_repo},
+    errors::AppError,
+    models::verification::{IdentifierVerification, VerifiedIdentifier},
+    scanner::Identifier,
+};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::env;
+use uuid::Uuid;
+
+/// How long a verification code stays valid.
+const CODE_TTL_MINUTES: i64 = 15;
+/// Wrong guesses allowed before a code is burned.
+const MAX_ATTEMPTS: i32 = 5;
+/// Minimum wait before another code is sent for the same identifier.
+const RESEND_INTERVAL_SECS: i64 = 60;
+/// Codes a user may be sent per hour across all their identifiers, so one account
+/// can't be used to message many addresses and numbers.
+const MAX_CODES_PER_HOUR: i64 = 10;
+
+#[derive(Deserialize)]
+pub struct VerifyIdentifierRequest {
+    pub identifier: Identifier,
+}
+
+#[derive(Serialize)]
+pub struct VerifyIdentifierResponse {
+    pub verification_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+pub struct ConfirmIdentifierRequest {
+    pub verification_id: Uuid,
+    pub code: String,
+}
+
+#[derive(Deserialize)]
+pub struct VerificationLinkQuery {
+    pub code: String,
+}
+
+/// Sends a one-time code to an email address or phone number so the user can
+/// prove they own it. Emails also get a link that verifies in one click.
+pub async fn request_verification(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Json(payload): Json<VerifyIdentifierRequest>,
+) -> Result<(StatusCode, Json<VerifyIdentifierResponse>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+    auth::handler::require_verified_email(&state, user_id).await?;
+
+    let identifier = payload.identifier.normalized().map_err(AppError::BadRequest)?;
+    let kind = identifier.kind();
+    let value = identifier.verifiable_value().ok_or_else(|| {
+        AppError::BadRequest("Only emails and phone numbers can be verified".to_string())
+    })?;
+
+    let code = format!("{:06}", OsRng.next_u32() % 1_000_000);
+    let code_hash = password::hash_password(&code).map_err(|_| AppError::InternalServerError)?;
+    let expires_at = Utc::now() + Duration::minutes(CODE_TTL_MINUTES);
+
+    // The limits are checked and the code recorded with the user's sends locked, so
+    // parallel requests can't all pass the checks
+    let mut tx = state.db_pool.begin().await.map_err(|_| AppError::InternalServerError)?;
+    verification_repo::lock_sends(&mut tx, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    let sent_last_hour = verification_repo::count_sent_since(&mut tx, user_id, Utc::now() - Duration::hours(1))
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    if sent_last_hour >= MAX_CODES_PER_HOUR {
+        return Err(AppError::TooManyRequests(
+            "Too many verification codes requested, try again later".to_string(),
+        ));
+    }
+
+    let last_sent = verification_repo::last_sent_at(&mut tx, user_id, kind, value)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    if let Some(last_sent) = last_sent {
+        let wait = RESEND_INTERVAL_SECS - (Utc::now() - last_sent).num_seconds();
+        if wait > 0 {
+            return Err(AppError::TooManyRequests(format!(
+                "A code was sent recently, try again in {} seconds",
+                wait
+            )));
+        }
+    }
+
+    let verification = verification_repo::create_verification(&mut tx, user_id, kind, value, &code_hash, expires_at)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    tx.commit().await.map_err(|_| AppError::InternalServerError)?;
+
+    let sent = match &identifier {
+        Identifier::Email { value } => queue_verification_email(&state, value, &verification, &code).await,
+        Identifier::Phone { value } => {
+            let body = format!(
+                "Your ShadowScan verification code is {}. It expires in {} minutes.",
+                code, CODE_TTL_MINUTES
+            );
+            state.sms.send(value, &body).await.map_err(|e| e.to_string())
+        }
+        _ => Err("only emails and phone numbers can be verified".to_string()),
+    };
+    if let Err(e) = sent {
+        tracing::error!("Failed to send verification code for {}: {}", verification.id, e);
+        return Err(AppError::InternalServerError);
+    }
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(VerifyIdentifierResponse {
+            verification_id: verification.id,
+            expires_at,
+            message: "Verification code sent".to_string(),
+        }),
+    ))
+}
+
+/// Queues the verification email, which holds the code and a link that verifies
+/// in one click.
+async fn queue_verification_email(
+    state: &AppState,
+    to: &str,
+    verification: &IdentifierVerification,
+    code: &str,
+) -> Result<(), String> {
+    let base_url = env::var("APP_BASE_URL").unwrap_or_else(|_| "http://localhost:3000".into());
+    let link = format!(
+        "{}/api/identifiers/verify/{}?code={}",
+        base_url.trim_end_matches('/'),
+        verification.id,
+        code
+    );
+    let context = serde_json::json!({ "code": code, "link": link, "ttl_minutes": CODE_TTL_MINUTES });
+    let message = state
+        .mail_templates
+        .render("verification", to, &context)
+        .map_err(|e| e.to_string())?;
+
+    let mut conn = state.db_pool.acquire().await.map_err(|e| e.to_string())?;
+    outbox_repo::enqueue_email(&mut conn, &message).await.map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Confirms a code sent by `request_verification`.
+pub async fn confirm_verification(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Json(payload): Json<ConfirmIdentifierRequest>,
+) -> Result<(StatusCode, Json<VerifiedIdentifier>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let verification = verification_repo::find_verification(&state.db_pool, payload.verification_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .filter(|verification| verification.user_id == user_id)
+        .ok_or_else(|| AppError::NotFound("Verification not found".to_string()))?;
+
+    let verified = check_code(&state, verification, &payload.code).await?;
+    Ok((StatusCode::OK, Json(verified)))
+}
+
+/// Target of the link in verification emails. The link itself carries the code, so
+/// it works without being logged in.
+pub async fn verify_link(
+    State(state): State<AppState>,
+    Path(verification_id): Path<Uuid>,
+    Query(query): Query<VerificationLinkQuery>,
+) -> Result<(StatusCode, Json<VerifiedIdentifier>), AppError> {
+    let verification = verification_repo::find_verification(&state.db_pool, verification_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .ok_or_else(|| AppError::NotFound("Verification not found".to_string()))?;
+
+    let verified = check_code(&state, verification, &query.code).await?;
+    Ok((StatusCode::OK, Json(verified)))
+}
+
+async fn check_code(
+    state: &AppState,
+    verification: IdentifierVerification,
+    code: &str,
+) -> Result<VerifiedIdentifier, AppError> {
+    if verification.consumed_at.is_some() {
+        return Err(AppError::BadRequest("This code has already been used".to_string()));
+    }
+    if verification.expires_at < Utc::now() {
+        return Err(AppError::BadRequest("This code has expired, request a new one".to_string()));
+    }
+    // Counted up front, whether or not the code turns out to be right
+    let claimed = verification_repo::claim_attempt(&state.db_pool, verification.id, MAX_ATTEMPTS)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    if !claimed {
+        return Err(AppError::TooManyRequests(
+            "Too many incorrect attempts, request a new code".to_string(),
+        ));
+    }
+
+    let matches = password::verify_password(code.trim(), &verification.code_hash)
+        .map_err(|_| AppError::InternalServerError)?;
+    if !matches {
+        return Err(AppError::BadRequest("Incorrect verification code".to_string()));
+    }
+
+    verification_repo::confirm_verification(&state.db_pool, verification.id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .ok_or_else(|| AppError::BadRequest("This code has already been used".to_string()))
+}
+
+pub async fn list_verified(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+) -> Result<(StatusCode, Json<Vec<VerifiedIdentifier>>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let verified = verification_repo::get_verified_identifiers(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    Ok((StatusCode::OK, Json(verified)))
+}
+
+pub async fn remove_verified(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let deleted = verification_repo::delete_verified_identifier(&state.db_pool, user_id, id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    if !deleted {
+        return Err(AppError::NotFound("Verified identifier not found".to_string()));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,5 +1,14 @@
 // src/handlers/mod.rs
 
+pub mod evidence;
 pub mod feedback;
+pub mod finding;
 pub mod health;
+pub mod identifier;
+pub mod profile;
 pub mod scan;
+pub mod schedule;
+pub mod score;
+pub mod session;
+pub mod suppression;
+pub mod takedown;
@@ -0,0 +1,180 @@
+// src/handlers/profile.rs
+
+use crate::{
+    app_state::AppState,
+    db::profile_repo,
+    errors::AppError,
+    handlers::scan::{normalize_identifiers, MAX_IDENTIFIERS_PER_SCAN},
+    models::profile::{IdentityProfile, ProfileIdentifier},
+    scanner::{Identifier, IdentifierKind},
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::{Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct ProfileRequest {
+    pub birth_year: Option<i32>,
+    /// The user's names, emails, phones, usernames and addresses.
+    #[serde(default)]
+    pub identifiers: Vec<Identifier>,
+    /// Other names the user is known by. Must be `name` identifiers.
+    #[serde(default)]
+    pub aliases: Vec<Identifier>,
+}
+
+#[derive(Deserialize)]
+pub struct AddIdentifierRequest {
+    pub identifier: Identifier,
+    #[serde(default)]
+    pub alias: bool,
+}
+
+#[derive(Serialize)]
+pub struct ProfileResponse {
+    #[serde(flatten)]
+    pub profile: IdentityProfile,
+    pub identifiers: Vec<ProfileIdentifier>,
+}
+
+fn validate_birth_year(birth_year: Option<i32>) -> Result<(), AppError> {
+    let current_year = Utc::now().year();
+    match birth_year {
+        Some(year) if !(1900..=current_year).contains(&year) => Err(AppError::BadRequest(format!(
+            "Birth year must be between 1900 and {}",
+            current_year
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn validate_alias(identifier: &Identifier) -> Result<(), AppError> {
+    if identifier.kind() != IdentifierKind::Name {
+        return Err(AppError::BadRequest("Aliases must be names".to_string()));
+    }
+    Ok(())
+}
+
+fn check_profile_size(len: usize) -> Result<(), AppError> {
+    if len > MAX_IDENTIFIERS_PER_SCAN {
+        return Err(AppError::BadRequest(format!(
+            "A profile can include at most {} identifiers",
+            MAX_IDENTIFIERS_PER_SCAN
+        )));
+    }
+    Ok(())
+}
+
+pub async fn get_profile(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+) -> Result<(StatusCode, Json<ProfileResponse>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let profile = profile_repo::get_profile(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .ok_or_else(|| AppError::NotFound("No profile saved yet".to_string()))?;
+    let identifiers = profile_repo::get_profile_identifiers(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(ProfileResponse { profile, identifiers })))
+}
+
+/// Creates or replaces the user's profile. A profile is scanned as a single scan, so
+/// it is held to the same identifier limit.
+pub async fn save_profile(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Json(payload): Json<ProfileRequest>,
+) -> Result<(StatusCode, Json<ProfileResponse>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    validate_birth_year(payload.birth_year)?;
+    for alias in &payload.aliases {
+        validate_alias(alias)?;
+    }
+
+    let primary = normalize_identifiers(payload.identifiers)?;
+    let aliases = normalize_identifiers(payload.aliases)?;
+    let mut identifiers: Vec<(Identifier, bool)> = primary.into_iter().map(|i| (i, false)).collect();
+    for alias in aliases {
+        if !identifiers.iter().any(|(existing, _)| *existing == alias) {
+            identifiers.push((alias, true));
+        }
+    }
+    check_profile_size(identifiers.len())?;
+
+    let (profile, identifiers) =
+        profile_repo::save_profile(&state.db_pool, user_id, payload.birth_year, &identifiers)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(ProfileResponse { profile, identifiers })))
+}
+
+pub async fn delete_profile(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+) -> Result<StatusCode, AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let deleted = profile_repo::delete_profile(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    if !deleted {
+        return Err(AppError::NotFound("No profile saved yet".to_string()));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn add_identifier(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Json(payload): Json<AddIdentifierRequest>,
+) -> Result<(StatusCode, Json<ProfileIdentifier>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    if payload.alias {
+        validate_alias(&payload.identifier)?;
+    }
+    let identifier = payload.identifier.normalized().map_err(AppError::BadRequest)?;
+
+    let existing: Vec<Identifier> = profile_repo::get_profile_identifiers(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .into_iter()
+        .map(|saved| saved.identifier)
+        .collect();
+    if existing.contains(&identifier) {
+        return Err(AppError::BadRequest("This identifier is already in your profile".to_string()));
+    }
+    check_profile_size(existing.len() + 1)?;
+
+    let saved = profile_repo::add_profile_identifier(&state.db_pool, user_id, &identifier, payload.alias)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    Ok((StatusCode::CREATED, Json(saved)))
+}
+
+pub async fn remove_identifier(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let deleted = profile_repo::delete_profile_identifier(&state.db_pool, user_id, id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    if !deleted {
+        return Err(AppError::NotFound("Profile identifier not found".to_string()));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -2,23 +2,75 @@
 
 use crate::{
     app_state::AppState,
-    db::scan_repo,
+    auth,
+    db::{job_repo, profile_repo, scan_repo, score_repo, verification_repo},
     errors::AppError,
-    models::scan::{Scan, ScanResult},
+    events::ScanNotification,
+    findings::diff::{ChangeSummary, ScanDiff},
+    models::{
+        scan::{Scan, ScanIdentifier, ScanResult, ScanStatus},
+        score::ScanScore,
+    },
+    scanner::Identifier,
 };
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
     Extension, Json,
 };
+use futures_util::Stream;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use std::collections::HashSet;
+use tokio::sync::broadcast::error::RecvError;
 use uuid::Uuid;
 
+/// Upper bound on identifiers per scan, to keep a single scan's fan-out reasonable.
+pub(crate) const MAX_IDENTIFIERS_PER_SCAN: usize = 25;
+
 #[derive(Deserialize)]
 pub struct ScanRequest {
-    // Define the fields for a scan request, e.g., email, username, etc.
-    pub email_to_scan: String,
+    /// Emails, phones, usernames, names and addresses to scan for, e.g.
+    /// `{"kind": "phone", "value": "(555) 123-4567"}`. When neither this nor
+    /// `email_to_scan` is given, the user's saved profile is scanned.
+    #[serde(default)]
+    pub identifiers: Vec<Identifier>,
+    /// Single email to scan for, kept for older clients.
+    pub email_to_scan: Option<String>,
+}
+
+impl ScanRequest {
+    /// The identifiers named in the request, validated and normalized. Empty when the
+    /// request names none, in which case the saved profile is scanned.
+    fn identifiers(self) -> Result<Vec<Identifier>, AppError> {
+        let requested = self
+            .email_to_scan
+            .map(|value| Identifier::Email { value })
+            .into_iter()
+            .chain(self.identifiers);
+        normalize_identifiers(requested)
+    }
+}
+
+/// Validates and normalizes every identifier, dropping duplicates.
+pub(crate) fn normalize_identifiers(
+    requested: impl IntoIterator<Item = Identifier>,
+) -> Result<Vec<Identifier>, AppError> {
+    let mut identifiers = Vec::new();
+    for identifier in requested {
+        let identifier = identifier.normalized().map_err(AppError::BadRequest)?;
+        if !identifiers.contains(&identifier) {
+            identifiers.push(identifier);
+        }
+    }
+
+    if identifiers.len() > MAX_IDENTIFIERS_PER_SCAN {
+        return Err(AppError::BadRequest(format!(
+            "A scan can include at most {} identifiers",
+            MAX_IDENTIFIERS_PER_SCAN
+        )));
+    }
+    Ok(identifiers)
 }
 
 #[derive(Serialize)]
@@ -33,75 +85,60 @@ pub async fn start_scan(
     Json(payload): Json<ScanRequest>,
 ) -> Result<(StatusCode, Json<ScanResponse>), AppError> {
     let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+    auth::handler::require_verified_email(&state, user_id).await?;
+
+    let mut identifiers = payload.identifiers()?;
+    if identifiers.is_empty() {
+        identifiers = profile_repo::get_profile_identifiers(&state.db_pool, user_id)
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .into_iter()
+            .map(|saved| saved.identifier)
+            .collect();
+    }
+    if identifiers.is_empty() {
+        return Err(AppError::BadRequest(
+            "Name at least one identifier or save a profile to scan".to_string(),
+        ));
+    }
 
-    let scan = scan_repo::create_scan(&state.db_pool, user_id)
+    // Emails and phone numbers may only be scanned by the person who owns them
+    let unverified = verification_repo::find_unverified(&state.db_pool, user_id, &identifiers)
         .await
         .map_err(|_| AppError::InternalServerError)?;
+    if !unverified.is_empty() {
+        let values: Vec<&str> = unverified.iter().filter_map(|i| i.verifiable_value()).collect();
+        return Err(AppError::Forbidden(format!(
+            "Verify ownership of {} before scanning it",
+            values.join(", ")
+        )));
+    }
 
-    let scan_id = scan.id;
-
-    // Spawn a background task to perform the scan
-    let app_state = state.clone();
-    tokio::spawn(async move {
-        run_scan(app_state, scan_id, payload.email_to_scan).await;
-    });
+    // The scan is picked up by a worker from the job queue
+    let scan = job_repo::enqueue_scan(&state.db_pool, user_id, &identifiers)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
 
     Ok((
         StatusCode::ACCEPTED,
         Json(ScanResponse {
-            scan_id,
-            message: "Scan started successfully".to_string(),
+            scan_id: scan.id,
+            message: "Scan queued successfully".to_string(),
         }),
     ))
 }
 
-async fn run_scan(app_state: AppState, scan_id: Uuid, email_to_scan: String) {
-    println!("Starting background scan for scan_id: {}", scan_id);
-
-    if let Err(e) = scan_repo::update_scan_status(&app_state.db_pool, scan_id, "in_progress").await {
-        eprintln!("Failed to update scan status: {}", e);
-        return;
-    }
-
-    // Simulate scanning different sources...
-    tokio::time::sleep(std::time::Duration::from_secs(10)).await;
-
-    // Simulate finding a result
-    let _ = scan_repo::create_scan_result(
-        &app_state.db_pool,
-        scan_id,
-        "email_leak",
-        json!({ "source": "Simulated Breach DB", "leaked_email": email_to_scan }),
-        "high",
-        Some("https://haveibeenpwned.com/"),
-    )
-    .await;
-
-    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-
-    // Simulate another finding
-    let _ = scan_repo::create_scan_result(
-        &app_state.db_pool,
-        scan_id,
-        "social_media",
-        json!({ "platform": "Twitter", "username": email_to_scan.split('@').next().unwrap_or("") }),
-        "low",
-        None,
-    )
-    .await;
-
-    if let Err(e) = scan_repo::update_scan_status(&app_state.db_pool, scan_id, "completed").await {
-        eprintln!("Failed to update scan status: {}", e);
-    }
-
-    println!("Finished background scan for scan_id: {}", scan_id);
-}
-
 #[derive(Serialize)]
 pub struct FullScanResult {
     #[serde(flatten)]
     pub scan: Scan,
+    pub targets: Vec<ScanIdentifier>,
     pub results: Vec<ScanResult>,
+    /// What changed since the user's previous completed scan, for completed scans
+    /// that have one.
+    pub changes: Option<ChangeSummary>,
+    /// Privacy score, for completed scans.
+    pub score: Option<ScanScore>,
 }
 
 pub async fn get_scan_results(
@@ -120,13 +157,238 @@ pub async fn get_scan_results(
         .await
         .map_err(|_| AppError::InternalServerError)?;
 
-    let mut full_results = Vec::new();
+    let mut full_results: Vec<FullScanResult> = Vec::new();
+    let mut previous_completed: Option<usize> = None;
     for scan in scans {
+        let targets = scan_repo::get_scan_targets(&state.db_pool, scan.id)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
         let results = scan_repo::get_scan_results_by_scan(&state.db_pool, scan.id)
             .await
             .map_err(|_| AppError::InternalServerError)?;
-        full_results.push(FullScanResult { scan, results });
+        let score = score_repo::get_score(&state.db_pool, scan.id)
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+
+        let mut changes = None;
+        if scan.status == ScanStatus::Completed {
+            if let Some(previous) = previous_completed.map(|i| &full_results[i]) {
+                changes = Some(ScanDiff::between(scan.id, &results, previous.scan.id, &previous.results).summary);
+            }
+            previous_completed = Some(full_results.len());
+        }
+        full_results.push(FullScanResult { scan, targets, results, changes, score });
     }
 
     Ok((StatusCode::OK, Json(full_results)))
 }
+
+#[derive(Deserialize)]
+pub struct DiffQuery {
+    /// Scan to compare against. Defaults to the previous completed scan.
+    pub against: Option<Uuid>,
+}
+
+/// Classifies a scan's findings as new, persisting or resolved relative to another
+/// of the user's scans.
+pub async fn scan_diff(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Path(scan_id): Path<Uuid>,
+    Query(query): Query<DiffQuery>,
+) -> Result<(StatusCode, Json<ScanDiff>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let scan = scan_repo::get_scan_by_id(&state.db_pool, scan_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .filter(|scan| scan.user_id == user_id)
+        .ok_or_else(|| AppError::NotFound("Scan not found".to_string()))?;
+
+    let against = match query.against {
+        Some(against_id) => scan_repo::get_scan_by_id(&state.db_pool, against_id)
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .filter(|scan| scan.user_id == user_id)
+            .ok_or_else(|| AppError::NotFound("Scan to compare against not found".to_string()))?,
+        None => scan_repo::get_previous_completed_scan(&state.db_pool, &scan)
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .ok_or_else(|| AppError::NotFound("No earlier completed scan to compare against".to_string()))?,
+    };
+
+    let current = scan_repo::get_scan_results_by_scan(&state.db_pool, scan.id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    let previous = scan_repo::get_scan_results_by_scan(&state.db_pool, against.id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    Ok((StatusCode::OK, Json(ScanDiff::between(scan.id, &current, against.id, &previous))))
+}
+
+/// Streams a scan's progress as Server-Sent Events: `status` on every status
+/// transition, `source` as each source starts and finishes, and `result` for every
+/// finding. Results stored before the client connected are sent first. The stream
+/// ends once the scan reaches a final status; if the stream falls behind, the scan
+/// is re-read so a missed final status still ends it.
+pub async fn scan_events(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Path(scan_id): Path<Uuid>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    // Subscribe before reading the current state so nothing is missed in between
+    let mut receiver = state.scan_events.subscribe();
+
+    let scan = scan_repo::get_scan_by_id(&state.db_pool, scan_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .filter(|scan| scan.user_id == user_id)
+        .ok_or_else(|| AppError::NotFound("Scan not found".to_string()))?;
+
+    let existing = scan_repo::get_scan_results_by_scan(&state.db_pool, scan_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    let pool = state.db_pool.clone();
+    let stream = async_stream::stream! {
+        let current = ScanNotification::Status { scan_id, status: scan.status };
+        yield Event::default().event("status").json_data(&current);
+
+        let mut sent = HashSet::new();
+        for result in existing {
+            sent.insert(result.id);
+            yield Event::default().event("result").json_data(&result);
+        }
+
+        if scan.status.is_terminal() {
+            return;
+        }
+
+        loop {
+            let notification = match receiver.recv().await {
+                Ok(notification) if notification.scan_id() == Some(scan_id) => Some(notification),
+                Ok(ScanNotification::Resync) => None,
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Event stream for scan_id {} skipped {} events", scan_id, skipped);
+                    None
+                }
+                Err(RecvError::Closed) => break,
+            };
+
+            // Events were skipped or lost, maybe the final status among them, so catch
+            // up from the database and end the stream if the scan has finished
+            let Some(notification) = notification else {
+                let (scan, results) = match (
+                    scan_repo::get_scan_by_id(&pool, scan_id).await,
+                    scan_repo::get_scan_results_by_scan(&pool, scan_id).await,
+                ) {
+                    (Ok(Some(scan)), Ok(results)) => (scan, results),
+                    (Ok(None), _) => break,
+                    (Err(e), _) | (_, Err(e)) => {
+                        tracing::error!("Failed to catch up event stream for scan_id {}: {}", scan_id, e);
+                        break;
+                    }
+                };
+                for result in results {
+                    if sent.insert(result.id) {
+                        yield Event::default().event("result").json_data(&result);
+                    }
+                }
+                if scan.status.is_terminal() {
+                    let current = ScanNotification::Status { scan_id, status: scan.status };
+                    yield Event::default().event("status").json_data(&current);
+                    break;
+                }
+                continue;
+            };
+
+            match notification {
+                ScanNotification::Status { status, .. } => {
+                    let done = status.is_terminal();
+                    yield Event::default().event("status").json_data(&notification);
+                    if done {
+                        break;
+                    }
+                }
+                ScanNotification::Source { .. } => {
+                    yield Event::default().event("source").json_data(&notification);
+                }
+                ScanNotification::Result { result_id, .. } => {
+                    if !sent.insert(result_id) {
+                        continue;
+                    }
+                    match scan_repo::get_scan_result_by_id(&pool, result_id).await {
+                        Ok(Some(result)) => yield Event::default().event("result").json_data(&result),
+                        Ok(None) => {}
+                        Err(e) => tracing::error!("Failed to load result {}: {}", result_id, e),
+                    }
+                }
+                // Handled by the catch-up above
+                ScanNotification::Resync => {}
+            }
+        }
+    };
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+#[derive(Serialize)]
+pub struct CancelScanResponse {
+    pub scan_id: Uuid,
+    pub status: ScanStatus,
+    /// Sources that finished before the cancellation. Their findings are kept.
+    pub completed_sources: Vec<String>,
+    /// Sources that had not finished and are being stopped. Empty if the scan
+    /// was cancelled before a worker picked it up, as then no source ran at all.
+    pub unfinished_sources: Vec<String>,
+    pub findings_kept: usize,
+}
+
+pub async fn cancel_scan(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Path(scan_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<CancelScanResponse>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let scan = match scan_repo::cancel_scan(&state.db_pool, scan_id, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+    {
+        Some(scan) => scan,
+        None => {
+            let existing = scan_repo::get_scan_by_id(&state.db_pool, scan_id)
+                .await
+                .map_err(|_| AppError::InternalServerError)?
+                .filter(|scan| scan.user_id == user_id);
+            return Err(match existing {
+                Some(scan) => AppError::BadRequest(format!("Scan is already {}", scan.status)),
+                None => AppError::NotFound("Scan not found".to_string()),
+            });
+        }
+    };
+
+    let sources = scan_repo::get_scan_sources(&state.db_pool, scan_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    let (completed, unfinished): (Vec<_>, Vec<_>) = sources
+        .into_iter()
+        .filter(|source| source.status != "failed")
+        .partition(|source| source.status == "completed");
+    let findings_kept = completed.iter().map(|source| source.findings_count as usize).sum();
+
+    Ok((
+        StatusCode::OK,
+        Json(CancelScanResponse {
+            scan_id,
+            status: scan.status,
+            completed_sources: completed.into_iter().map(|source| source.source).collect(),
+            unfinished_sources: unfinished.into_iter().map(|source| source.source).collect(),
+            findings_kept,
+        }),
+    ))
+}
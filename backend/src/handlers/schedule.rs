@@ -0,0 +1,103 @@
+// src/handlers/schedule.rs
+
+use crate::{
+    app_state::AppState,
+    auth,
+    db::schedule_repo,
+    errors::AppError,
+    models::schedule::{self, ScanSchedule, ScheduleFrequency},
+};
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct ScheduleRequest {
+    pub frequency: ScheduleFrequency,
+    /// Required for the `cron` frequency. Five fields as in crontab, with 0 or 7 for
+    /// Sunday, e.g. `"0 6 * * 1"` for Mondays at 06:00 UTC.
+    pub cron_expression: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// When daily, weekly and monthly runs are counted from. Defaults to now, so
+    /// the first scheduled scan runs one period later.
+    pub starts_at: Option<DateTime<Utc>>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+pub async fn get_schedule(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+) -> Result<(StatusCode, Json<ScanSchedule>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let schedule = schedule_repo::get_schedule(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .ok_or_else(|| AppError::NotFound("No scan schedule set".to_string()))?;
+    Ok((StatusCode::OK, Json(schedule)))
+}
+
+/// Creates or replaces the user's scan schedule. Scheduled scans cover the saved
+/// profile.
+pub async fn save_schedule(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Json(payload): Json<ScheduleRequest>,
+) -> Result<(StatusCode, Json<ScanSchedule>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+    auth::handler::require_verified_email(&state, user_id).await?;
+
+    let cron_expression = match (payload.frequency, payload.cron_expression.as_deref()) {
+        (ScheduleFrequency::Cron, Some(expression)) => {
+            schedule::validate_cron(expression).map_err(AppError::BadRequest)?;
+            Some(expression.trim())
+        }
+        (ScheduleFrequency::Cron, None) => {
+            return Err(AppError::BadRequest("A cron schedule needs a cron_expression".to_string()))
+        }
+        (_, Some(_)) => {
+            return Err(AppError::BadRequest(
+                "cron_expression is only used with the cron frequency".to_string(),
+            ))
+        }
+        (_, None) => None,
+    };
+
+    let now = Utc::now();
+    let starts_at = payload.starts_at.unwrap_or(now);
+    let next_run_at = schedule::next_run_after(payload.frequency, cron_expression, starts_at, now)
+        .map_err(AppError::BadRequest)?;
+
+    let schedule = schedule_repo::upsert_schedule(
+        &state.db_pool,
+        user_id,
+        payload.frequency,
+        cron_expression,
+        payload.enabled,
+        starts_at,
+        next_run_at,
+    )
+    .await
+    .map_err(|_| AppError::InternalServerError)?;
+    Ok((StatusCode::OK, Json(schedule)))
+}
+
+pub async fn delete_schedule(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+) -> Result<StatusCode, AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let deleted = schedule_repo::delete_schedule(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    if !deleted {
+        return Err(AppError::NotFound("No scan schedule set".to_string()));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,18 @@
+// src/handlers/score.rs
+
+use crate::{app_state::AppState, db::score_repo, errors::AppError, models::score::ScanScore};
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use uuid::Uuid;
+
+/// The user's privacy score after each completed scan, oldest first, for trend charts.
+pub async fn score_history(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+) -> Result<(StatusCode, Json<Vec<ScanScore>>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let history = score_repo::get_score_history(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    Ok((StatusCode::OK, Json(history)))
+}
@@ -0,0 +1,79 @@
+// src/handlers/session.rs
+
+use crate::{
+    app_state::AppState,
+    auth::middleware::SessionId,
+    db::session_repo,
+    errors::AppError,
+    models::session::Session,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Serialize)]
+pub struct SessionItem {
+    #[serde(flatten)]
+    pub session: Session,
+    /// Whether the request was made with this session's token.
+    pub current: bool,
+}
+
+#[derive(Serialize)]
+pub struct RevokedSessions {
+    pub revoked: u64,
+}
+
+/// The user's signed-in devices, most recently used first.
+pub async fn list_sessions(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Extension(SessionId(current)): Extension<SessionId>,
+) -> Result<(StatusCode, Json<Vec<SessionItem>>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let sessions = session_repo::get_live_sessions(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    let items = sessions
+        .into_iter()
+        .map(|session| SessionItem { current: session.id == current, session })
+        .collect();
+    Ok((StatusCode::OK, Json(items)))
+}
+
+/// Signs one of the user's devices out. Its access token stops working
+/// immediately and its refresh token can't be exchanged any more.
+pub async fn revoke_session(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Path(session_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let revoked = session_repo::revoke_session(&state.db_pool, user_id, session_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    if !revoked {
+        return Err(AppError::NotFound("Session not found".to_string()));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Signs every device out except the one making the request.
+pub async fn revoke_other_sessions(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Extension(SessionId(current)): Extension<SessionId>,
+) -> Result<(StatusCode, Json<RevokedSessions>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let revoked = session_repo::revoke_other_sessions(&state.db_pool, user_id, current)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    Ok((StatusCode::OK, Json(RevokedSessions { revoked })))
+}
@@ -0,0 +1,139 @@
+// src/handlers/suppression.rs
+
+use crate::{
+    app_state::AppState,
+    db::{finding_repo, suppression_repo},
+    errors::AppError,
+    models::{
+        finding::{FindingState, TrackedFinding},
+        scan::ScanResult,
+        suppression::{PrecisionStat, Suppression},
+    },
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Serialize)]
+pub struct SuppressedItem {
+    #[serde(flatten)]
+    pub suppression: Suppression,
+    /// The tracked finding that was reported, if it still exists.
+    pub finding: Option<TrackedFinding>,
+}
+
+#[derive(Serialize)]
+pub struct PrecisionStats {
+    pub sources: Vec<PrecisionStat>,
+    pub rules: Vec<PrecisionStat>,
+}
+
+/// Suppresses a result the user reported as a false positive, so later scans skip
+/// it, and ignores its tracked finding.
+pub(crate) async fn suppress_result(
+    state: &AppState,
+    user_id: Uuid,
+    result: &ScanResult,
+    feedback_id: Uuid,
+) -> Result<Suppression, AppError> {
+    let suppression = suppression_repo::create_suppression(
+        &state.db_pool,
+        user_id,
+        &result.fingerprint,
+        &result.source,
+        result.finding_id,
+        Some(feedback_id),
+    )
+    .await
+    .map_err(|_| AppError::InternalServerError)?;
+
+    if let Some(finding_id) = result.finding_id {
+        let finding = finding_repo::get_finding_by_id(&state.db_pool, finding_id)
+            .await
+            .map_err(|_| AppError::InternalServerError)?
+            .filter(|finding| finding.state != FindingState::Ignored);
+        if let Some(finding) = finding {
+            let note = "Reported as a false positive";
+            let ignored = finding_repo::set_state(&state.db_pool, finding.id, finding.state, FindingState::Ignored, Some(note), Some(user_id))
+                .await
+                .map_err(|_| AppError::InternalServerError)?;
+            // Remembered so withdrawing the report doesn't reopen a finding the user
+            // had ignored on their own
+            if ignored.is_some() {
+                suppression_repo::mark_ignored_finding(&state.db_pool, suppression.id)
+                    .await
+                    .map_err(|_| AppError::InternalServerError)?;
+                return Ok(Suppression { ignored_finding: true, ..suppression });
+            }
+        }
+    }
+    Ok(suppression)
+}
+
+/// Everything the user has reported as a false positive.
+pub async fn list_suppressions(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+) -> Result<(StatusCode, Json<Vec<SuppressedItem>>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let suppressions = suppression_repo::get_suppressions_by_user(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    let mut items = Vec::with_capacity(suppressions.len());
+    for suppression in suppressions {
+        let finding = match suppression.finding_id {
+            Some(finding_id) => finding_repo::get_finding_by_id(&state.db_pool, finding_id)
+                .await
+                .map_err(|_| AppError::InternalServerError)?,
+            None => None,
+        };
+        items.push(SuppressedItem { suppression, finding });
+    }
+    Ok((StatusCode::OK, Json(items)))
+}
+
+/// Withdraws a false positive report: later scans report the fingerprint again,
+/// and the finding is reopened if the report is what ignored it.
+pub async fn remove_suppression(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Path(suppression_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let suppression = suppression_repo::delete_suppression(&state.db_pool, user_id, suppression_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .ok_or_else(|| AppError::NotFound("Suppression not found".to_string()))?;
+
+    // Only a finding the report ignored, and that is still ignored, is reopened
+    if let (Some(finding_id), true) = (suppression.finding_id, suppression.ignored_finding) {
+        let note = "False positive report withdrawn";
+        finding_repo::set_state(&state.db_pool, finding_id, FindingState::Ignored, FindingState::Open, Some(note), Some(user_id))
+            .await
+            .map_err(|_| AppError::InternalServerError)?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// How often each source and risk rule produces findings the user reported as
+/// false positives.
+pub async fn precision_stats(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+) -> Result<(StatusCode, Json<PrecisionStats>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let sources = suppression_repo::get_source_precision(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    let rules = suppression_repo::get_rule_precision(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    Ok((StatusCode::OK, Json(PrecisionStats { sources, rules })))
+}
@@ -0,0 +1,358 @@
+// src/handlers/takedown.rs
+
+use crate::{
+    app_state::AppState,
+    db::{
+        finding_repo, outbox_repo, profile_repo,
+        takedown_repo::{self, NewTakedownRequest},
+        user_repo,
+    },
+    errors::AppError,
+    handlers::finding::get_own_finding,
+    models::{
+        finding::{FindingState, TrackedFinding},
+        outbox::QueuedEmail,
+        scan::FindingType,
+        takedown::{
+            RemovalCheck, TakedownChannel, TakedownEscalation, TakedownRequest, TakedownResponse,
+            TakedownResponseKind, TakedownStatus,
+        },
+    },
+    takedown::{
+        deadline::{self, Regime},
+        Requester, TakedownContext, TakedownLetter,
+    },
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Longest jurisdiction a request can record, e.g. `us-ca`.
+const MAX_JURISDICTION_LEN: usize = 20;
+
+#[derive(Deserialize, Default)]
+pub struct TakedownLetterRequest {
+    /// Jurisdiction whose law to invoke, e.g. `eu` for a user living in the EU.
+    /// Defaults to the broker's jurisdiction.
+    pub jurisdiction: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateTakedownRequest {
+    pub finding_id: Uuid,
+    pub channel: TakedownChannel,
+    pub jurisdiction: Option<String>,
+    /// When the request went out; defaults to now.
+    pub sent_at: Option<DateTime<Utc>>,
+    /// Have the service email the letter to the broker now, with replies going to
+    /// the user. Requires the `email` channel.
+    #[serde(default)]
+    pub send: bool,
+}
+
+#[derive(Deserialize)]
+pub struct AddResponseRequest {
+    pub kind: TakedownResponseKind,
+    pub note: Option<String>,
+    /// Defaults to now.
+    pub received_at: Option<DateTime<Utc>>,
+    /// Days the broker extended the deadline by, for `extension` responses.
+    /// Defaults to the longest extension the law allows.
+    pub extension_days: Option<i32>,
+}
+
+#[derive(Serialize)]
+pub struct TakedownRequestDetail {
+    #[serde(flatten)]
+    pub request: TakedownRequest,
+    pub responses: Vec<TakedownResponse>,
+    /// Delivery status of the letter, when the service emailed it.
+    pub email: Option<QueuedEmail>,
+    /// Drafted once the request goes overdue.
+    pub escalation: Option<TakedownEscalation>,
+    /// Re-scans of the broker verifying the removal, oldest first.
+    pub checks: Vec<RemovalCheck>,
+}
+
+/// Renders an erasure or opt-out request for a data broker listing as plain text,
+/// HTML and a ready-to-send email.
+pub async fn generate_takedown(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Path(finding_id): Path<Uuid>,
+    payload: Option<Json<TakedownLetterRequest>>,
+) -> Result<(StatusCode, Json<TakedownLetter>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+    let payload = payload.map(|Json(payload)| payload).unwrap_or_default();
+
+    let finding = get_broker_finding(&state, user_id, finding_id).await?;
+    let letter = render_request(&state, user_id, &finding, payload.jurisdiction.as_deref()).await?;
+    Ok((StatusCode::OK, Json(letter)))
+}
+
+/// Records that the user sent a takedown request for a finding, starting the
+/// broker's statutory clock.
+pub async fn create_takedown_request(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Json(payload): Json<CreateTakedownRequest>,
+) -> Result<(StatusCode, Json<TakedownRequest>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let sent_at = match (payload.send, payload.sent_at) {
+        (true, Some(_)) => {
+            return Err(AppError::BadRequest("sent_at cannot be set when the service sends the letter".to_string()))
+        }
+        (true, None) if payload.channel != TakedownChannel::Email => {
+            return Err(AppError::BadRequest("Only the email channel can be sent by the service".to_string()))
+        }
+        (_, sent_at) => sent_at.unwrap_or_else(Utc::now),
+    };
+    if sent_at > Utc::now() {
+        return Err(AppError::BadRequest("sent_at cannot be in the future".to_string()));
+    }
+
+    let finding = get_broker_finding(&state, user_id, payload.finding_id).await?;
+    let pending = takedown_repo::has_pending_request(&state.db_pool, finding.id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    if pending {
+        return Err(AppError::BadRequest(
+            "A takedown request for this finding is still awaiting a response".to_string(),
+        ));
+    }
+
+    let letter = render_request(&state, user_id, &finding, payload.jurisdiction.as_deref()).await?;
+    let email = match (&letter.email, payload.send) {
+        (Some(email), true) => Some(email),
+        (None, true) => {
+            return Err(AppError::BadRequest(
+                "The broker has no contact address; use its opt-out form instead".to_string(),
+            ))
+        }
+        (_, false) => None,
+    };
+
+    // The letter is only queued if the request is recorded too
+    let mut tx = state.db_pool.begin().await.map_err(|_| AppError::InternalServerError)?;
+    let email_id = match email {
+        Some(email) => Some(
+            outbox_repo::enqueue_email(&mut tx, email)
+                .await
+                .map_err(|_| AppError::InternalServerError)?
+                .id,
+        ),
+        None => None,
+    };
+    let request = takedown_repo::create_request(
+        &mut tx,
+        &NewTakedownRequest {
+            user_id,
+            finding_id: finding.id,
+            broker: &finding.source,
+            jurisdiction: &letter.jurisdiction,
+            channel: payload.channel,
+            subject: &letter.subject,
+            body: &letter.text,
+            sent_at,
+            deadline_at: deadline::deadline(&letter.jurisdiction, sent_at, 0),
+            email_id,
+        },
+    )
+    .await
+    .map_err(|_| AppError::InternalServerError)?
+    // Another request for the finding was recorded since the check above; dropping
+    // the transaction takes the queued letter back out
+    .ok_or_else(|| {
+        AppError::BadRequest("A takedown request for this finding is still awaiting a response".to_string())
+    })?;
+    tx.commit().await.map_err(|_| AppError::InternalServerError)?;
+
+    if finding.state != FindingState::RemovalRequested {
+        finding_repo::set_state(
+            &state.db_pool,
+            finding.id,
+            finding.state,
+            FindingState::RemovalRequested,
+            Some("Takedown request sent"),
+            Some(user_id),
+        )
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    }
+
+    Ok((StatusCode::CREATED, Json(request)))
+}
+
+pub async fn list_takedown_requests(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+) -> Result<(StatusCode, Json<Vec<TakedownRequest>>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let requests = takedown_repo::get_requests_by_user(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    Ok((StatusCode::OK, Json(requests)))
+}
+
+pub async fn get_takedown_request(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Path(request_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<TakedownRequestDetail>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let request = get_own_request(&state, user_id, request_id).await?;
+    let detail = request_detail(&state, request).await?;
+    Ok((StatusCode::OK, Json(detail)))
+}
+
+/// Records the broker's answer to a request: an acknowledgement, a deadline
+/// extension, completion or refusal.
+pub async fn add_takedown_response(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<String>,
+    Path(request_id): Path<Uuid>,
+    Json(payload): Json<AddResponseRequest>,
+) -> Result<(StatusCode, Json<TakedownRequestDetail>), AppError> {
+    let user_id = Uuid::parse_str(&user_id).map_err(|_| AppError::InternalServerError)?;
+
+    let received_at = payload.received_at.unwrap_or_else(Utc::now);
+    if received_at > Utc::now() {
+        return Err(AppError::BadRequest("received_at cannot be in the future".to_string()));
+    }
+
+    let mut request = get_own_request(&state, user_id, request_id).await?;
+    if received_at < request.sent_at {
+        return Err(AppError::BadRequest("received_at is before the request was sent".to_string()));
+    }
+    match payload.kind {
+        TakedownResponseKind::Acknowledged => {
+            if request.status == TakedownStatus::Sent {
+                request.status = TakedownStatus::Acknowledged;
+            }
+        }
+        TakedownResponseKind::Extension => extend(&mut request, payload.extension_days)?,
+        TakedownResponseKind::Completed => {
+            request.status = TakedownStatus::Completed;
+            // Verify the broker's word right away
+            if request.next_check_at.is_some() {
+                request.next_check_at = Some(Utc::now());
+            }
+        }
+        TakedownResponseKind::Refused => request.status = TakedownStatus::Refused,
+        TakedownResponseKind::Other => {}
+    }
+
+    let note = payload.note.as_deref().map(str::trim).filter(|note| !note.is_empty());
+    let request = takedown_repo::record_response(&state.db_pool, &request, payload.kind, note, received_at)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    let detail = request_detail(&state, request).await?;
+    Ok((StatusCode::CREATED, Json(detail)))
+}
+
+/// Applies a deadline extension, which the law allows once and only while the
+/// broker still owes an answer.
+fn extend(request: &mut TakedownRequest, days: Option<i32>) -> Result<(), AppError> {
+    if !request.status.is_pending() {
+        return Err(AppError::BadRequest(
+            "Only requests still awaiting a response can be extended".to_string(),
+        ));
+    }
+    if request.extension_days > 0 {
+        return Err(AppError::BadRequest("The deadline has already been extended".to_string()));
+    }
+    let max_days = Regime::for_jurisdiction(&request.jurisdiction).max_extension_days();
+    if max_days == 0 {
+        return Err(AppError::BadRequest(format!(
+            "No deadline extension is allowed for jurisdiction {}",
+            request.jurisdiction
+        )));
+    }
+    let days = i64::from(days.unwrap_or(max_days as i32));
+    if days <= 0 || days > max_days {
+        return Err(AppError::BadRequest(format!(
+            "extension_days must be between 1 and {}",
+            max_days
+        )));
+    }
+
+    request.status = TakedownStatus::Extended;
+    request.extension_days = days as i32;
+    request.deadline_at = deadline::deadline(&request.jurisdiction, request.sent_at, days);
+    request.next_check_at = request.next_check_at.map(|next| next.max(request.deadline_at));
+    Ok(())
+}
+
+async fn get_own_request(state: &AppState, user_id: Uuid, request_id: Uuid) -> Result<TakedownRequest, AppError> {
+    takedown_repo::get_request(&state.db_pool, request_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .filter(|request| request.user_id == user_id)
+        .ok_or_else(|| AppError::NotFound("Takedown request not found".to_string()))
+}
+
+async fn request_detail(state: &AppState, request: TakedownRequest) -> Result<TakedownRequestDetail, AppError> {
+    let responses = takedown_repo::get_responses(&state.db_pool, request.id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    let email = match request.email_id {
+        Some(email_id) => outbox_repo::get_email(&state.db_pool, email_id)
+            .await
+            .map_err(|_| AppError::InternalServerError)?,
+        None => None,
+    };
+    let escalation = takedown_repo::get_escalation(&state.db_pool, request.id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    let checks = takedown_repo::get_removal_checks(&state.db_pool, request.id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+    Ok(TakedownRequestDetail { request, responses, email, escalation, checks })
+}
+
+/// Loads one of the user's findings, making sure it is a data broker listing.
+async fn get_broker_finding(state: &AppState, user_id: Uuid, finding_id: Uuid) -> Result<TrackedFinding, AppError> {
+    let finding = get_own_finding(state, user_id, finding_id).await?;
+    if finding.finding_type != FindingType::DataBroker {
+        return Err(AppError::BadRequest(
+            "Takedown letters can only be generated for data broker listings".to_string(),
+        ));
+    }
+    Ok(finding)
+}
+
+async fn render_request(
+    state: &AppState,
+    user_id: Uuid,
+    finding: &TrackedFinding,
+    jurisdiction: Option<&str>,
+) -> Result<TakedownLetter, AppError> {
+    if jurisdiction.is_some_and(|jurisdiction| jurisdiction.trim().chars().count() > MAX_JURISDICTION_LEN) {
+        return Err(AppError::BadRequest(format!(
+            "jurisdiction must be at most {} characters",
+            MAX_JURISDICTION_LEN
+        )));
+    }
+    let user = user_repo::find_user_by_id(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?
+        .ok_or(AppError::InternalServerError)?;
+    let identifiers = profile_repo::get_profile_identifiers(&state.db_pool, user_id)
+        .await
+        .map_err(|_| AppError::InternalServerError)?;
+
+    let context = TakedownContext::new(Requester::new(&user, &identifiers), finding, jurisdiction);
+    state.takedown_templates.render_request(&context).map_err(|e| {
+        tracing::error!("Failed to render takedown letter for finding {}: {}", finding.id, e);
+        AppError::InternalServerError
+    })
+}
@@ -0,0 +1,92 @@
+// src/jobs/deadlines.rs
+
+use crate::{
+    db::{evidence_repo, finding_repo, profile_repo, takedown_repo, user_repo},
+    models::takedown::TakedownRequest,
+    takedown::{
+        self, CapturedEvidence, EscalationContext, Requester, SentRequest, TakedownContext, TakedownLetter,
+        TakedownTemplates,
+    },
+};
+use sqlx::PgPool;
+use std::{sync::Arc, time::Duration};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// How often takedown deadlines are checked.
+const POLL_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Flags takedown requests whose statutory deadline passed without the broker
+/// completing them, and drafts a complaint to the supervisory authority for each,
+/// citing the evidence captured of the listing, until `shutdown` is cancelled.
+pub async fn run_deadline_monitor(pool: PgPool, templates: Arc<TakedownTemplates>, shutdown: CancellationToken) {
+    tracing::debug!("takedown deadline monitor started");
+    while !shutdown.is_cancelled() {
+        loop {
+            match flag_next_overdue(&pool, &templates).await {
+                Ok(true) => continue,
+                Ok(false) => break,
+                Err(e) => {
+                    tracing::error!("Failed to check takedown deadlines: {}", e);
+                    break;
+                }
+            }
+        }
+        tokio::select! {
+            _ = shutdown.cancelled() => {}
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+        }
+    }
+    tracing::debug!("takedown deadline monitor stopped");
+}
+
+/// Handles the request that went overdue first, if any. Returns whether one was
+/// overdue.
+async fn flag_next_overdue(pool: &PgPool, templates: &TakedownTemplates) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let Some(request) = takedown_repo::lock_overdue_request(&mut tx).await? else {
+        return Ok(false);
+    };
+
+    takedown_repo::mark_overdue(&mut tx, request.id).await?;
+    // A letter that fails to render leaves the request flagged without a draft
+    match escalation_letter(pool, templates, &request).await? {
+        Some((letter, evidence_ids)) => {
+            let escalation = takedown_repo::create_escalation(&mut tx, request.id, &letter, &evidence_ids).await?;
+            tracing::info!("Takedown request {} is overdue, drafted escalation {}", request.id, escalation.id);
+        }
+        None => tracing::info!("Takedown request {} is overdue", request.id),
+    }
+    tx.commit().await?;
+    Ok(true)
+}
+
+async fn escalation_letter(
+    pool: &PgPool,
+    templates: &TakedownTemplates,
+    request: &TakedownRequest,
+) -> Result<Option<(TakedownLetter, Vec<Uuid>)>, sqlx::Error> {
+    let Some(finding) = finding_repo::get_finding_by_id(pool, request.finding_id).await? else {
+        return Ok(None);
+    };
+    let Some(user) = user_repo::find_user_by_id(pool, request.user_id).await? else {
+        return Ok(None);
+    };
+    let identifiers = profile_repo::get_profile_identifiers(pool, request.user_id).await?;
+    let responses = takedown_repo::get_responses(pool, request.id).await?;
+    let snapshots = evidence_repo::get_snapshots(pool, finding.id).await?;
+    let evidence = takedown::escalation_evidence(&snapshots, request.sent_at);
+
+    let context = EscalationContext {
+        letter: TakedownContext::new(Requester::new(&user, &identifiers), &finding, Some(&request.jurisdiction)),
+        request: SentRequest::new(request, &responses),
+        evidence: evidence.iter().map(|snapshot| CapturedEvidence::new(snapshot)).collect(),
+    };
+    match templates.render_escalation(&context) {
+        Ok(letter) => Ok(Some((letter, evidence.iter().map(|snapshot| snapshot.id).collect()))),
+        Err(e) => {
+            tracing::error!("Failed to render escalation for takedown request {}: {}", request.id, e);
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,7 @@
+// src/jobs/mod.rs
+
+pub mod deadlines;
+pub mod outbox;
+pub mod removal;
+pub mod scheduler;
+pub mod worker;
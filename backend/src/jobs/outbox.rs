@@ -0,0 +1,77 @@
+// src/jobs/outbox.rs
+
+use crate::{db::outbox_repo, mail::Mailer};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use sqlx::PgPool;
+use std::{sync::Arc, time::Duration};
+use tokio_util::sync::CancellationToken;
+
+/// How often the outbox is checked for messages to deliver.
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// Attempts before a message is marked failed.
+const MAX_ATTEMPTS: i32 = 8;
+/// Wait before the first retry, doubled after every further failure.
+const INITIAL_BACKOFF_SECS: i64 = 60;
+const MAX_BACKOFF_SECS: i64 = 6 * 60 * 60;
+
+/// Delivers queued email through `mailer` until `shutdown` is cancelled, retrying
+/// failed messages with exponential backoff. Messages that fail permanently, say
+/// because the server rejected the recipient, are marked failed right away. A message stays locked while it is
+/// sent, so several workers never deliver it twice.
+pub async fn run_mail_delivery(pool: PgPool, mailer: Arc<dyn Mailer>, shutdown: CancellationToken) {
+    tracing::debug!("mail delivery started");
+    while !shutdown.is_cancelled() {
+        loop {
+            match deliver_next(&pool, mailer.as_ref()).await {
+                Ok(true) if !shutdown.is_cancelled() => continue,
+                Ok(_) => break,
+                Err(e) => {
+                    tracing::error!("Failed to deliver queued email: {}", e);
+                    break;
+                }
+            }
+        }
+        tokio::select! {
+            _ = shutdown.cancelled() => {}
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+        }
+    }
+    tracing::debug!("mail delivery stopped");
+}
+
+/// Attempts the next due message, if any. Returns whether there was one.
+async fn deliver_next(pool: &PgPool, mailer: &dyn Mailer) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let Some(email) = outbox_repo::lock_due_email(&mut tx).await? else {
+        return Ok(false);
+    };
+
+    match mailer.send(&email.message()).await {
+        Ok(()) => {
+            outbox_repo::mark_sent(&mut tx, email.id).await?;
+            tracing::info!("Delivered email {} to {}", email.id, email.recipient);
+        }
+        Err(e) => {
+            let retry_at = if e.permanent { None } else { retry_at(email.attempts + 1, Utc::now()) };
+            match retry_at {
+                Some(at) => tracing::warn!("Delivering email {} failed, retrying at {}: {}", email.id, at, e),
+                None if e.permanent => tracing::error!("Email {} can't be delivered: {}", email.id, e),
+                None => tracing::error!("Giving up on email {} after {} attempts: {}", email.id, MAX_ATTEMPTS, e),
+            }
+            outbox_repo::record_failure(&mut tx, email.id, &e.to_string(), retry_at).await?;
+        }
+    }
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// When to retry after `attempts` failures, or None once they are used up.
+fn retry_at(attempts: i32, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if attempts >= MAX_ATTEMPTS {
+        return None;
+    }
+    let backoff = INITIAL_BACKOFF_SECS
+        .saturating_mul(1 << (attempts - 1).clamp(0, 30))
+        .min(MAX_BACKOFF_SECS);
+    Some(now + ChronoDuration::seconds(backoff))
+}
@@ -0,0 +1,342 @@
+// src/jobs/removal.rs
+
+use crate::{
+    db::{finding_repo, profile_repo, takedown_repo, verification_repo},
+    evidence::{self, BlobStore, Capture},
+    matching::MatchProfile,
+    models::{
+        finding::{FindingState, TrackedFinding},
+        takedown::{RemovalCheckOutcome, TakedownRequest, TakedownStatus},
+    },
+    scanner::{
+        broker::{ListedRecord, SearchPage},
+        Identifier, ScanTarget, Scanner, ScannerRegistry,
+    },
+};
+use chrono::{Duration as ChronoDuration, Utc};
+use reqwest::Url;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use sqlx::PgPool;
+use std::{collections::HashSet, sync::Arc, time::Duration};
+use tokio_util::sync::CancellationToken;
+
+/// How often due removal checks are looked for.
+const POLL_INTERVAL: Duration = Duration::from_secs(15 * 60);
+/// How long a claimed check may take before another worker retries it.
+const CLAIM_LEASE_MINUTES: i64 = 60;
+/// Re-check interval while the broker still lists the finding.
+const RECHECK_LISTED_DAYS: i64 = 7;
+/// Re-check interval after the removal was verified, watching for relisting.
+const RECHECK_REMOVED_DAYS: i64 = 30;
+/// Retry interval when the broker could not be searched.
+const RETRY_FAILED_HOURS: i64 = 24;
+
+/// What a removal check searched for and what the broker returned, kept as
+/// evidence with the check.
+#[derive(Debug, Serialize)]
+struct Evidence {
+    source: String,
+    searched: Vec<Identifier>,
+    /// Records on the pages searched, whether or not they match the user.
+    records: usize,
+    /// The listing as found, when it is still up.
+    listing: Option<Value>,
+    source_link: Option<String>,
+    /// Evidence snapshots of the page still showing the listing, or of every page
+    /// searched once it is gone.
+    snapshot_ids: Vec<uuid::Uuid>,
+    error: Option<String>,
+    #[serde(skip)]
+    captures: Vec<Capture>,
+}
+
+/// Re-scans the broker behind each takedown request once its deadline passes and
+/// periodically after: findings whose listing is gone are resolved, and ones that
+/// reappear after a verified removal are reopened as relisted. The pages showing
+/// the listing, or showing it gone, are kept in `blob_store` as evidence. Runs until
+/// `shutdown` is cancelled.
+pub async fn run_removal_checks(
+    pool: PgPool,
+    scanners: ScannerRegistry,
+    blob_store: Arc<dyn BlobStore>,
+    shutdown: CancellationToken,
+) {
+    tracing::debug!("removal checks started");
+    while !shutdown.is_cancelled() {
+        loop {
+            match check_next_due(&pool, &scanners, blob_store.as_ref(), &shutdown).await {
+                Ok(true) => continue,
+                Ok(false) => break,
+                Err(e) => {
+                    tracing::error!("Failed to run removal check: {}", e);
+                    break;
+                }
+            }
+        }
+        tokio::select! {
+            _ = shutdown.cancelled() => {}
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+        }
+    }
+    tracing::debug!("removal checks stopped");
+}
+
+/// Runs the most overdue removal check, if any. Returns whether one was due.
+async fn check_next_due(
+    pool: &PgPool,
+    scanners: &ScannerRegistry,
+    blob_store: &dyn BlobStore,
+    shutdown: &CancellationToken,
+) -> Result<bool, sqlx::Error> {
+    let lease_until = Utc::now() + ChronoDuration::minutes(CLAIM_LEASE_MINUTES);
+    let Some(request) = takedown_repo::claim_due_check(pool, lease_until).await? else {
+        return Ok(false);
+    };
+    let finding = finding_repo::get_finding_by_id(pool, request.finding_id).await?;
+    let Some(finding) = finding.filter(|finding| finding.state != FindingState::Ignored) else {
+        // Nothing left to watch once the user ignores the finding
+        let mut conn = pool.acquire().await?;
+        takedown_repo::finish_check(&mut conn, request.id, request.status, None).await?;
+        return Ok(true);
+    };
+
+    let mut evidence = search_broker(pool, scanners, &finding, shutdown).await?;
+    // Left to the lease, so another worker checks again
+    if shutdown.is_cancelled() {
+        return Ok(false);
+    }
+    let outcome = match (&evidence.error, &evidence.listing) {
+        (Some(_), _) => RemovalCheckOutcome::Failed,
+        (None, Some(_)) => RemovalCheckOutcome::Listed,
+        (None, None) => RemovalCheckOutcome::Removed,
+    };
+
+    for capture in &evidence.captures {
+        match evidence::record_capture(pool, blob_store, finding.id, None, capture).await {
+            Ok(snapshot) => evidence.snapshot_ids.push(snapshot.id),
+            Err(e) => tracing::error!("Failed to keep evidence for finding {}: {}", finding.id, e),
+        }
+    }
+
+    let mut tx = pool.begin().await?;
+    let evidence = serde_json::to_value(&evidence).unwrap_or_default();
+    let check = takedown_repo::create_removal_check(&mut tx, &request, outcome, &evidence).await?;
+    let (status, next_check_in) = match outcome {
+        RemovalCheckOutcome::Failed => (request.status, Some(ChronoDuration::hours(RETRY_FAILED_HOURS))),
+        RemovalCheckOutcome::Removed => {
+            let note = format!("Removal from {} verified by a re-scan", request.broker);
+            finding_repo::mark_removed(&mut tx, finding.id, &note).await?;
+            (TakedownStatus::Completed, Some(ChronoDuration::days(RECHECK_REMOVED_DAYS)))
+        }
+        // Back after a verified removal: reopened for a new request, so this one is done
+        RemovalCheckOutcome::Listed if finding.removal_verified_at.is_some() => {
+            let note = format!("Listed again by {} after its removal was verified", request.broker);
+            finding_repo::mark_relisted(&mut tx, finding.id, &note).await?;
+            (request.status, None)
+        }
+        RemovalCheckOutcome::Listed => (request.status, Some(ChronoDuration::days(RECHECK_LISTED_DAYS))),
+    };
+    let next_check_at = next_check_in.map(|interval| Utc::now() + interval);
+    takedown_repo::finish_check(&mut tx, request.id, status, next_check_at).await?;
+    tx.commit().await?;
+
+    log_check(&request, &finding, outcome, check.id);
+    Ok(true)
+}
+
+/// Searches the finding's broker for the user again and reports whether the
+/// listing is still there, as `listing_status` tells.
+async fn search_broker(
+    pool: &PgPool,
+    scanners: &ScannerRegistry,
+    finding: &TrackedFinding,
+    shutdown: &CancellationToken,
+) -> Result<Evidence, sqlx::Error> {
+    let mut evidence = Evidence {
+        source: finding.source.clone(),
+        searched: Vec::new(),
+        records: 0,
+        listing: None,
+        source_link: None,
+        snapshot_ids: Vec::new(),
+        error: None,
+        captures: Vec::new(),
+    };
+    let Some(scanner) = scanners.broker(&finding.source) else {
+        evidence.error = Some("The broker is no longer in the catalog".to_string());
+        return Ok(evidence);
+    };
+
+    let mut target = removal_target(pool, finding).await?.restricted_to(scanner.supported_kinds());
+    // Search the way the listing was found, while the user still has that identifier
+    let found_by: Option<Identifier> =
+        finding.details.get("searched").and_then(|searched| serde_json::from_value(searched.clone()).ok());
+    if let Some(found_by) = found_by.filter(|found_by| target.identifiers.contains(found_by)) {
+        target.identifiers = vec![found_by];
+    }
+    if target.is_empty() {
+        evidence.error = Some("Nothing in the profile that the broker can search for".to_string());
+        return Ok(evidence);
+    }
+    evidence.searched = target.identifiers.clone();
+
+    let pages = match scanner.search_pages(&target, shutdown).await {
+        Ok(pages) => pages,
+        Err(e) => {
+            evidence.error = Some(e.to_string());
+            return Ok(evidence);
+        }
+    };
+    evidence.records = pages.iter().map(|page| page.records.len()).sum();
+
+    match listing_status(&finding.details, finding.source_link.as_deref(), &pages) {
+        ListingStatus::Listed { page, listed } => {
+            let source_link = listed.listing_url.as_ref().map_or_else(|| page.capture.url.clone(), Url::to_string);
+            evidence.listing = Some(Value::Object(listed.record.clone()));
+            evidence.source_link = Some(source_link);
+            evidence.captures.push(page.capture.clone());
+        }
+        ListingStatus::Gone => evidence.captures = pages.iter().map(|page| page.capture.clone()).collect(),
+        ListingStatus::Unknown(error) => evidence.error = Some(error),
+    }
+    Ok(evidence)
+}
+
+/// What searching a broker again showed of a finding's listing.
+#[derive(Debug)]
+pub enum ListingStatus<'a> {
+    /// Still up, as `listed` on `page`.
+    Listed { page: &'a SearchPage, listed: &'a ListedRecord },
+    /// On every page searched the broker either said nothing matched or only listed
+    /// other records.
+    Gone,
+    /// The pages can't tell, for the reason given: an error status, or a page with no
+    /// records that doesn't say nothing matched either, like a block page or a
+    /// changed layout.
+    Unknown(String),
+}
+
+/// Looks for the listing a finding with `details` and `source_link` was made from
+/// among every record on `pages`, not just the ones matching the user well enough
+/// for a scan to report them.
+pub fn listing_status<'a>(details: &Value, source_link: Option<&str>, pages: &'a [SearchPage]) -> ListingStatus<'a> {
+    let listed = pages.iter().find_map(|page| {
+        let listed = page.records.iter().find(|listed| is_same_listing(details, source_link, listed))?;
+        Some((page, listed))
+    });
+    if let Some((page, listed)) = listed {
+        return ListingStatus::Listed { page, listed };
+    }
+    if pages.is_empty() {
+        return ListingStatus::Unknown("The broker was not searched".to_string());
+    }
+    let unusable = pages.iter().find_map(|page| {
+        if page.found_nothing() {
+            None
+        } else if !page.is_success() {
+            Some(format!("{} answered with status {}", page.capture.url, page.capture.status))
+        } else if page.records.is_empty() {
+            Some(format!("No records could be read from {}", page.capture.url))
+        } else {
+            None
+        }
+    });
+    match unusable {
+        Some(error) => ListingStatus::Unknown(error),
+        None => ListingStatus::Gone,
+    }
+}
+
+/// Whether `listed` is the listing a finding with `details` and `source_link` was
+/// made from: the same page on the broker when both link to one, else a record
+/// sharing an address, phone number or email with the one found, or just the name
+/// when the finding listed none of those. Edits to the rest of the listing, like
+/// the age going up, don't make it a different one.
+pub fn is_same_listing(details: &Value, source_link: Option<&str>, listed: &ListedRecord) -> bool {
+    let tracked_url = details.get("listing_url").and_then(Value::as_str);
+    if let Some(listing_url) = &listed.listing_url {
+        if [tracked_url, source_link].contains(&Some(listing_url.as_str())) {
+            return true;
+        }
+        if tracked_url.is_some() {
+            return false;
+        }
+    }
+
+    let Some(tracked) = details.get("record").and_then(Value::as_object) else {
+        return false;
+    };
+    let contacts = contact_keys(tracked);
+    if contacts.is_empty() {
+        let name = |record: &Map<String, Value>| record.get("name").and_then(Value::as_str).map(normalize);
+        return name(tracked).is_some() && name(tracked) == name(&listed.record);
+    }
+    !contacts.is_disjoint(&contact_keys(&listed.record))
+}
+
+/// The record's address, phone numbers and emails, normalized for comparison.
+fn contact_keys(record: &Map<String, Value>) -> HashSet<String> {
+    let values = |field: &str| -> Vec<&str> {
+        match record.get(field) {
+            Some(Value::String(value)) => vec![value.as_str()],
+            Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        }
+    };
+
+    let mut keys = HashSet::new();
+    for address in values("home_address") {
+        keys.insert(format!("address:{}", normalize(address)));
+    }
+    for phone in values("phone") {
+        let digits: String = phone.chars().filter(char::is_ascii_digit).collect();
+        // National numbers, ignoring a leading +1 or other country code
+        keys.insert(format!("phone:{}", &digits[digits.len().saturating_sub(10)..]));
+    }
+    for email in values("email") {
+        keys.insert(format!("email:{}", normalize(email)));
+    }
+    keys.retain(|key| !key.ends_with(':'));
+    keys
+}
+
+fn normalize(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// The user's saved profile, minus emails and phone numbers they haven't verified,
+/// as scheduled scans search it.
+async fn removal_target(pool: &PgPool, finding: &TrackedFinding) -> Result<ScanTarget, sqlx::Error> {
+    let saved: Vec<Identifier> = profile_repo::get_profile_identifiers(pool, finding.user_id)
+        .await?
+        .into_iter()
+        .map(|saved| saved.identifier)
+        .collect();
+    let unverified = verification_repo::find_unverified(pool, finding.user_id, &saved).await?;
+    let birth_year = profile_repo::get_profile(pool, finding.user_id)
+        .await?
+        .and_then(|profile| profile.birth_year);
+
+    Ok(ScanTarget {
+        identifiers: saved.iter().filter(|identifier| !unverified.contains(identifier)).cloned().collect(),
+        profile: MatchProfile { identifiers: saved, birth_year },
+    })
+}
+
+fn log_check(request: &TakedownRequest, finding: &TrackedFinding, outcome: RemovalCheckOutcome, check_id: uuid::Uuid) {
+    match outcome {
+        RemovalCheckOutcome::Removed => {
+            tracing::info!("Finding {} is gone from {} (check {})", finding.id, request.broker, check_id)
+        }
+        RemovalCheckOutcome::Listed if finding.removal_verified_at.is_some() => {
+            tracing::info!("Finding {} was relisted by {} (check {})", finding.id, request.broker, check_id)
+        }
+        RemovalCheckOutcome::Listed => {
+            tracing::info!("Finding {} is still listed by {} (check {})", finding.id, request.broker, check_id)
+        }
+        RemovalCheckOutcome::Failed => {
+            tracing::warn!("Could not check {} for finding {} (check {})", request.broker, finding.id, check_id)
+        }
+    }
+}
@@ -0,0 +1,108 @@
+// src/jobs/scheduler.rs
+
+use crate::{
+    db::{job_repo, profile_repo, scan_repo, schedule_repo, verification_repo},
+    models::schedule::ScanSchedule,
+    scanner::Identifier,
+};
+use chrono::Utc;
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// How often due schedules are looked for.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Enqueues scans for schedules as they come due until `shutdown` is cancelled.
+/// A schedule is advanced in the same transaction that enqueues its scan, so a
+/// restart, or several schedulers running at once, never fire a run twice.
+pub async fn run_scheduler(pool: PgPool, shutdown: CancellationToken) {
+    tracing::debug!("scheduler started");
+    while !shutdown.is_cancelled() {
+        loop {
+            match fire_next_due(&pool).await {
+                Ok(true) => continue,
+                Ok(false) => break,
+                Err(e) => {
+                    tracing::error!("Failed to run due scan schedules: {}", e);
+                    break;
+                }
+            }
+        }
+        tokio::select! {
+            _ = shutdown.cancelled() => {}
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+        }
+    }
+    tracing::debug!("scheduler stopped");
+}
+
+/// Handles the most overdue schedule, if any. Returns whether one was due.
+async fn fire_next_due(pool: &PgPool) -> Result<bool, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let Some(schedule) = schedule_repo::lock_due_schedule(&mut tx).await? else {
+        return Ok(false);
+    };
+
+    let next_run_at = match schedule.next_run_after(Utc::now()) {
+        Ok(next_run_at) => next_run_at,
+        Err(e) => {
+            tracing::warn!("Disabling scan schedule for user {}: {}", schedule.user_id, e);
+            schedule_repo::disable_schedule(&mut tx, schedule.user_id).await?;
+            tx.commit().await?;
+            return Ok(true);
+        }
+    };
+
+    let scan_id = match scheduled_identifiers(pool, &schedule).await? {
+        Some(identifiers) => {
+            let scan = job_repo::enqueue_scan_in(&mut tx, schedule.user_id, &identifiers).await?;
+            tracing::info!("Queued scheduled scan {} for user {}", scan.id, schedule.user_id);
+            Some(scan.id)
+        }
+        None => None,
+    };
+
+    schedule_repo::advance_schedule(&mut tx, schedule.user_id, next_run_at, scan_id).await?;
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// What a scheduled run should scan: the user's profile, minus any emails and
+/// phone numbers they haven't verified. None when this run should be skipped.
+async fn scheduled_identifiers(
+    pool: &PgPool,
+    schedule: &ScanSchedule,
+) -> Result<Option<Vec<Identifier>>, sqlx::Error> {
+    let user_id = schedule.user_id;
+
+    if scan_repo::has_active_scan(pool, user_id).await? {
+        tracing::info!("Skipping scheduled scan for user {}: a scan is already running", user_id);
+        return Ok(None);
+    }
+
+    let identifiers: Vec<Identifier> = profile_repo::get_profile_identifiers(pool, user_id)
+        .await?
+        .into_iter()
+        .map(|saved| saved.identifier)
+        .collect();
+    let unverified = verification_repo::find_unverified(pool, user_id, &identifiers).await?;
+    if !unverified.is_empty() {
+        tracing::warn!(
+            "Scheduled scan for user {} leaves out {} unverified identifiers",
+            user_id,
+            unverified.len()
+        );
+    }
+    let identifiers: Vec<Identifier> = identifiers
+        .iter()
+        .filter(|identifier| !unverified.contains(identifier))
+        .cloned()
+        .collect();
+
+    if identifiers.is_empty() {
+        tracing::info!("Skipping scheduled scan for user {}: nothing in the profile to scan", user_id);
+        return Ok(None);
+    }
+    Ok(Some(identifiers))
+}
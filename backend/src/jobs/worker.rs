@@ -0,0 +1,295 @@
+// src/jobs/worker.rs
+
+use crate::{
+    db::{job_repo, profile_repo, scan_repo},
+    events::{ScanEventHub, ScanNotification},
+    evidence::BlobStore,
+    matching::MatchProfile,
+    models::{job::ScanJob, scan::ScanStatus},
+    risk::RiskRules,
+    scanner::{
+        runner::{self, ScanOutcome},
+        Identifier, ScanTarget, ScannerRegistry,
+    },
+};
+use sqlx::PgPool;
+use std::{env, sync::Arc, time::Duration};
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    task::JoinSet,
+};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct WorkerConfig {
+    /// Number of jobs processed concurrently.
+    pub concurrency: usize,
+    /// How long a claimed job stays locked without a heartbeat.
+    pub lease: Duration,
+    pub heartbeat_interval: Duration,
+    /// How long to wait before polling again when the queue is empty.
+    pub poll_interval: Duration,
+    /// Delay before the first retry; doubled on every further attempt.
+    pub retry_base_delay: Duration,
+    pub retry_max_delay: Duration,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            lease: Duration::from_secs(60),
+            heartbeat_interval: Duration::from_secs(15),
+            poll_interval: Duration::from_secs(2),
+            retry_base_delay: Duration::from_secs(30),
+            retry_max_delay: Duration::from_secs(30 * 60),
+        }
+    }
+}
+
+impl WorkerConfig {
+    /// Defaults, overridden by `SCAN_WORKER_CONCURRENCY` and `SCAN_JOB_LEASE_SECS` if set.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Some(concurrency) = env::var("SCAN_WORKER_CONCURRENCY").ok().and_then(|v| v.parse().ok()) {
+            config.concurrency = concurrency;
+        }
+        if let Some(lease) = env::var("SCAN_JOB_LEASE_SECS").ok().and_then(|v| v.parse().ok()) {
+            config.lease = Duration::from_secs(lease);
+            config.heartbeat_interval = config.lease / 4;
+        }
+        config
+    }
+
+    fn retry_delay(&self, attempts: i32) -> Duration {
+        let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+        self.retry_base_delay
+            .saturating_mul(2u32.pow(exponent))
+            .min(self.retry_max_delay)
+    }
+}
+
+/// Recovers orphaned jobs, then runs `config.concurrency` worker loops and a
+/// reaper that keeps recovering jobs whose worker disappeared. Returns once
+/// `shutdown` is cancelled and every in-flight job has finished.
+pub async fn run_workers(
+    pool: PgPool,
+    scanners: ScannerRegistry,
+    risk_rules: Arc<RiskRules>,
+    blob_store: Arc<dyn BlobStore>,
+    config: WorkerConfig,
+    shutdown: CancellationToken,
+) {
+    match job_repo::recover_orphaned_jobs(&pool).await {
+        Ok(0) => {}
+        Ok(recovered) => tracing::info!("Recovered {} orphaned scan jobs", recovered),
+        Err(e) => tracing::error!("Failed to recover orphaned scan jobs: {}", e),
+    }
+
+    let scan_events = ScanEventHub::new();
+    scan_events.spawn_listener(pool.clone());
+
+    let mut workers = JoinSet::new();
+    for _ in 0..config.concurrency {
+        let worker = Worker {
+            id: format!("worker-{}", Uuid::new_v4()),
+            pool: pool.clone(),
+            scanners: scanners.clone(),
+            risk_rules: risk_rules.clone(),
+            blob_store: blob_store.clone(),
+            scan_events: scan_events.clone(),
+            config: config.clone(),
+        };
+        workers.spawn(worker.run(shutdown.clone()));
+    }
+
+    let reaper = tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            let mut interval = tokio::time::interval(config.lease);
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = interval.tick() => {}
+                }
+                match job_repo::recover_orphaned_jobs(&pool).await {
+                    Ok(0) => {}
+                    Ok(recovered) => tracing::warn!("Recovered {} orphaned scan jobs", recovered),
+                    Err(e) => tracing::error!("Failed to recover orphaned scan jobs: {}", e),
+                }
+            }
+        }
+    });
+
+    while workers.join_next().await.is_some() {}
+    let _ = reaper.await;
+}
+
+struct Worker {
+    id: String,
+    pool: PgPool,
+    scanners: ScannerRegistry,
+    risk_rules: Arc<RiskRules>,
+    blob_store: Arc<dyn BlobStore>,
+    scan_events: ScanEventHub,
+    config: WorkerConfig,
+}
+
+impl Worker {
+    async fn run(self, shutdown: CancellationToken) {
+        tracing::debug!("{} started", self.id);
+        while !shutdown.is_cancelled() {
+            match job_repo::claim_next_job(&self.pool, &self.id, self.config.lease).await {
+                Ok(Some(job)) => {
+                    self.process(job).await;
+                    continue;
+                }
+                Ok(None) => {}
+                Err(e) => tracing::error!("{} failed to claim a job: {}", self.id, e),
+            }
+            tokio::select! {
+                _ = shutdown.cancelled() => {}
+                _ = tokio::time::sleep(self.config.poll_interval) => {}
+            }
+        }
+        tracing::debug!("{} stopped", self.id);
+    }
+
+    /// The scan's identifiers, with the user's saved profile added to what records
+    /// are matched against.
+    async fn load_target(&self, scan_id: Uuid) -> Result<ScanTarget, sqlx::Error> {
+        let identifiers: Vec<Identifier> = scan_repo::get_scan_targets(&self.pool, scan_id)
+            .await?
+            .into_iter()
+            .map(|target| target.identifier)
+            .collect();
+
+        let mut profile = MatchProfile {
+            identifiers: identifiers.clone(),
+            birth_year: None,
+        };
+        if let Some(scan) = scan_repo::get_scan_by_id(&self.pool, scan_id).await? {
+            if let Some(saved) = profile_repo::get_profile(&self.pool, scan.user_id).await? {
+                profile.birth_year = saved.birth_year;
+            }
+            for saved in profile_repo::get_profile_identifiers(&self.pool, scan.user_id).await? {
+                if !profile.identifiers.contains(&saved.identifier) {
+                    profile.identifiers.push(saved.identifier);
+                }
+            }
+        }
+
+        Ok(ScanTarget { identifiers, profile })
+    }
+
+    async fn process(&self, job: ScanJob) {
+        // Cancelled when the user cancels the scan or the lease is lost, so the runner
+        // can stop its sources
+        let cancel = CancellationToken::new();
+        let watcher = tokio::spawn(watch_for_cancellation(
+            self.scan_events.subscribe(),
+            job.scan_id,
+            cancel.clone(),
+        ));
+        let heartbeat = tokio::spawn(heartbeat_loop(
+            self.pool.clone(),
+            job.id,
+            self.id.clone(),
+            self.config.clone(),
+            cancel.clone(),
+        ));
+
+        let result = match self.load_target(job.scan_id).await {
+            Ok(target) => {
+                runner::run_scan(
+                    &self.pool,
+                    &self.scanners,
+                    &self.risk_rules,
+                    self.blob_store.as_ref(),
+                    job.scan_id,
+                    target,
+                    cancel,
+                )
+                .await
+            }
+            Err(e) => Err(format!("failed to load scan targets: {}", e)),
+        };
+
+        heartbeat.abort();
+        watcher.abort();
+
+        let outcome = match result {
+            Ok(ScanOutcome::Completed) => job_repo::complete_job(&self.pool, job.id, &self.id, "succeeded").await,
+            Ok(ScanOutcome::Cancelled) => job_repo::complete_job(&self.pool, job.id, &self.id, "cancelled").await,
+            Err(error) => {
+                let retry_after = self.config.retry_delay(job.attempts);
+                job_repo::fail_job(&self.pool, job.id, &self.id, &error, retry_after)
+                    .await
+                    .map(|retrying| {
+                        if retrying {
+                            tracing::warn!(
+                                "Scan job {} failed (attempt {}), retrying in {:?}: {}",
+                                job.id, job.attempts, retry_after, error
+                            );
+                        } else {
+                            tracing::error!("Scan job {} failed permanently: {}", job.id, error);
+                        }
+                    })
+            }
+        };
+        if let Err(e) = outcome {
+            tracing::error!("Failed to record outcome of scan job {}: {}", job.id, e);
+        }
+    }
+}
+
+/// Cancels `cancel` as soon as the scan is reported cancelled.
+async fn watch_for_cancellation(
+    mut receiver: broadcast::Receiver<ScanNotification>,
+    scan_id: Uuid,
+    cancel: CancellationToken,
+) {
+    loop {
+        match receiver.recv().await {
+            Ok(ScanNotification::Status { scan_id: id, status }) if id == scan_id && status == ScanStatus::Cancelled => {
+                cancel.cancel();
+                return;
+            }
+            Ok(_) | Err(RecvError::Lagged(_)) => {}
+            Err(RecvError::Closed) => return,
+        }
+    }
+}
+
+/// Keeps the job's lease alive, and cancels the run once the lease is lost. Also
+/// notices cancellations whose notification was missed, e.g. while the events
+/// listener was reconnecting.
+async fn heartbeat_loop(
+    pool: PgPool,
+    job_id: Uuid,
+    worker_id: String,
+    config: WorkerConfig,
+    cancel: CancellationToken,
+) {
+    let mut interval = tokio::time::interval(config.heartbeat_interval);
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        match job_repo::heartbeat(&pool, job_id, &worker_id, config.lease).await {
+            Ok(Some(scan_status)) => {
+                if scan_status == ScanStatus::Cancelled {
+                    cancel.cancel();
+                }
+            }
+            // The reaper may already have handed the job to another worker, so the
+            // scan is stopped here rather than run twice
+            Ok(None) => {
+                tracing::warn!("{} lost the lease on scan job {}, stopping the scan", worker_id, job_id);
+                cancel.cancel();
+                return;
+            }
+            Err(e) => tracing::error!("Heartbeat for scan job {} failed: {}", job_id, e),
+        }
+    }
+}
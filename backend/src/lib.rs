@@ -2,8 +2,20 @@
 
 pub mod app_state;
 pub mod auth;
+pub mod brokers;
 pub mod db;
 pub mod errors;
+pub mod events;
+pub mod evidence;
+pub mod findings;
 pub mod handlers;
+pub mod jobs;
+pub mod mail;
+pub mod matching;
 pub mod models;
+pub mod risk;
 pub mod routes;
+pub mod scanner;
+pub mod sms;
+pub mod startup;
+pub mod takedown;
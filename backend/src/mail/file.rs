@@ -0,0 +1,38 @@
+// src/mail/file.rs
+
+use crate::mail::{build_message, EmailMessage, MailError, Mailer};
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Development mailer that writes each message to its own `.eml` file.
+pub struct FileMailer {
+    dir: PathBuf,
+    from: Mailbox,
+}
+
+impl FileMailer {
+    pub fn new(dir: impl Into<PathBuf>, from: Mailbox) -> Self {
+        Self { dir: dir.into(), from }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), MailError> {
+        let contents = build_message(&self.from, message)?.formatted();
+
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| MailError::new(format!("failed to create {}: {}", self.dir.display(), e)))?;
+        let path = self.dir.join(format!(
+            "{}-{}.eml",
+            chrono::Utc::now().format("%Y%m%dT%H%M%S%.3f"),
+            Uuid::new_v4().simple()
+        ));
+        tokio::fs::write(&path, contents)
+            .await
+            .map_err(|e| MailError::new(format!("failed to write {}: {}", path.display(), e)))
+    }
+}
@@ -0,0 +1,61 @@
+// src/mail/mbox.rs
+
+use crate::mail::{build_message, EmailMessage, MailError, Mailer};
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use std::path::PathBuf;
+use tokio::{fs::OpenOptions, io::AsyncWriteExt, sync::Mutex};
+
+/// Development mailer that appends every message to a single mbox file, which
+/// mail clients can open directly.
+pub struct MboxMailer {
+    path: PathBuf,
+    from: Mailbox,
+    // Keeps concurrent messages from interleaving in the file
+    lock: Mutex<()>,
+}
+
+impl MboxMailer {
+    pub fn new(path: impl Into<PathBuf>, from: Mailbox) -> Self {
+        Self { path: path.into(), from, lock: Mutex::new(()) }
+    }
+}
+
+#[async_trait]
+impl Mailer for MboxMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), MailError> {
+        let formatted = build_message(&self.from, message)?.formatted();
+        let formatted = String::from_utf8_lossy(&formatted).replace("\r\n", "\n");
+
+        let mut entry = format!(
+            "From {} {}\n",
+            self.from.email,
+            chrono::Utc::now().format("%a %b %e %H:%M:%S %Y")
+        );
+        for line in formatted.lines() {
+            // mboxrd quoting, so body lines can't be mistaken for message separators
+            if line.trim_start_matches('>').starts_with("From ") {
+                entry.push('>');
+            }
+            entry.push_str(line);
+            entry.push('\n');
+        }
+        entry.push('\n');
+
+        let _guard = self.lock.lock().await;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| MailError::new(format!("failed to open {}: {}", self.path.display(), e)))?;
+        file.write_all(entry.as_bytes())
+            .await
+            .map_err(|e| MailError::new(format!("failed to write {}: {}", self.path.display(), e)))?;
+        // Flushed before the lock is released: dropping a tokio file doesn't wait
+        // for the write to land, so the next entry could overtake this one
+        file.flush()
+            .await
+            .map_err(|e| MailError::new(format!("failed to write {}: {}", self.path.display(), e)))
+    }
+}
@@ -0,0 +1,115 @@
+// src/mail/mod.rs
+
+// Outbound email. The API queues messages in the `email_outbox` table and the
+// worker delivers them through a `Mailer`: SMTP in production, or `.eml` files or
+// an mbox file during development.
+
+pub mod file;
+pub mod mbox;
+pub mod smtp;
+pub mod templates;
+
+use async_trait::async_trait;
+use lettre::{
+    message::{header::ContentType, Mailbox, MultiPart},
+    Message,
+};
+use serde::Serialize;
+use std::{env, fmt, sync::Arc};
+
+pub const DEFAULT_FROM: &str = "ShadowScan <no-reply@shadowscan.local>";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub text_body: String,
+    pub html_body: Option<String>,
+    /// Where replies should go when that is not the sender, e.g. the user a
+    /// takedown letter is sent for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct MailError {
+    pub message: String,
+    /// Sending the message again can't succeed, e.g. the server rejected the
+    /// recipient or the message can't be built.
+    pub permanent: bool,
+}
+
+impl MailError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into(), permanent: false }
+    }
+
+    pub fn permanent(message: impl Into<String>) -> Self {
+        Self { message: message.into(), permanent: true }
+    }
+}
+
+impl fmt::Display for MailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for MailError {}
+
+/// Outbound email delivery.
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, message: &EmailMessage) -> Result<(), MailError>;
+}
+
+/// Builds the MIME message for `message`, sent from `from`.
+pub fn build_message(from: &Mailbox, message: &EmailMessage) -> Result<Message, MailError> {
+    let parse = |address: &str| {
+        address
+            .parse::<Mailbox>()
+            .map_err(|e| MailError::permanent(format!("invalid address {}: {}", address, e)))
+    };
+
+    let mut builder = Message::builder()
+        .from(from.clone())
+        .to(parse(&message.to)?)
+        .subject(&message.subject);
+    if let Some(reply_to) = &message.reply_to {
+        builder = builder.reply_to(parse(reply_to)?);
+    }
+    let built = match &message.html_body {
+        Some(html) => builder.multipart(MultiPart::alternative_plain_html(message.text_body.clone(), html.clone())),
+        None => builder.header(ContentType::TEXT_PLAIN).body(message.text_body.clone()),
+    };
+    built.map_err(|e| MailError::permanent(format!("failed to build message: {}", e)))
+}
+
+/// Mailer selected by `MAIL_TRANSPORT`, sending from `MAIL_FROM`:
+///
+/// - `file` (default): each message is written to `MAIL_OUTBOX_DIR` (default
+///   `mail_outbox`) as an `.eml` file.
+/// - `mbox`: messages are appended to the mbox file at `MAIL_MBOX_FILE` (default
+///   `mail_outbox.mbox`).
+/// - `smtp`: messages are delivered through the server set by the `SMTP_*`
+///   variables, see [`smtp::SmtpConfig::from_env`].
+pub fn mailer_from_env() -> Arc<dyn Mailer> {
+    let from = env::var("MAIL_FROM").unwrap_or_else(|_| DEFAULT_FROM.into());
+    let from: Mailbox = from.parse().expect("MAIL_FROM must be a valid address");
+
+    match env::var("MAIL_TRANSPORT").unwrap_or_else(|_| "file".into()).as_str() {
+        "file" => {
+            let dir = env::var("MAIL_OUTBOX_DIR").unwrap_or_else(|_| "mail_outbox".into());
+            Arc::new(file::FileMailer::new(dir, from))
+        }
+        "mbox" => {
+            let path = env::var("MAIL_MBOX_FILE").unwrap_or_else(|_| "mail_outbox.mbox".into());
+            Arc::new(mbox::MboxMailer::new(path, from))
+        }
+        "smtp" => {
+            let config = smtp::SmtpConfig::from_env().expect("Invalid SMTP configuration");
+            Arc::new(smtp::SmtpMailer::new(&config, from).expect("Failed to create SMTP mailer"))
+        }
+        other => panic!("Unknown MAIL_TRANSPORT {}, expected file, mbox or smtp", other),
+    }
+}
@@ -0,0 +1,117 @@
+// src/mail/smtp.rs
+
+use crate::mail::{build_message, EmailMessage, MailError, Mailer};
+use async_trait::async_trait;
+use lettre::{
+    message::Mailbox,
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Tokio1Executor,
+};
+use std::{env, time::Duration};
+
+/// How the connection to the SMTP server is secured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    /// Plain text, for local mail catchers only.
+    None,
+    /// Upgrade with STARTTLS, usually on port 587.
+    StartTls,
+    /// TLS from the start, usually on port 465.
+    Tls,
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub timeout: Duration,
+}
+
+impl SmtpConfig {
+    /// Reads `SMTP_HOST` (required), `SMTP_SECURITY` (`starttls` by default, `tls`
+    /// or `none`), `SMTP_PORT` (defaults to the usual port for the security mode),
+    /// `SMTP_USERNAME`, `SMTP_PASSWORD` and `SMTP_TIMEOUT_SECS` (default 30).
+    pub fn from_env() -> Result<Self, MailError> {
+        let host = env::var("SMTP_HOST").map_err(|_| MailError::new("SMTP_HOST must be set"))?;
+        let security = match env::var("SMTP_SECURITY").unwrap_or_else(|_| "starttls".into()).as_str() {
+            "none" => SmtpSecurity::None,
+            "starttls" => SmtpSecurity::StartTls,
+            "tls" => SmtpSecurity::Tls,
+            other => {
+                return Err(MailError::new(format!(
+                    "unknown SMTP_SECURITY {}, expected none, starttls or tls",
+                    other
+                )))
+            }
+        };
+        let port = match env::var("SMTP_PORT") {
+            Ok(port) => port.parse().map_err(|_| MailError::new(format!("invalid SMTP_PORT {}", port)))?,
+            Err(_) => match security {
+                SmtpSecurity::None => 25,
+                SmtpSecurity::StartTls => 587,
+                SmtpSecurity::Tls => 465,
+            },
+        };
+        let timeout = env::var("SMTP_TIMEOUT_SECS")
+            .ok()
+            .and_then(|secs| secs.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(Duration::from_secs(30));
+
+        Ok(Self {
+            host,
+            port,
+            security,
+            username: env::var("SMTP_USERNAME").ok(),
+            password: env::var("SMTP_PASSWORD").ok(),
+            timeout,
+        })
+    }
+}
+
+/// Delivers mail through an SMTP server, reusing pooled connections.
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(config: &SmtpConfig, from: Mailbox) -> Result<Self, MailError> {
+        let builder = match config.security {
+            SmtpSecurity::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+            SmtpSecurity::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                .map_err(|e| MailError::new(format!("failed to set up STARTTLS for {}: {}", config.host, e)))?,
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+                .map_err(|e| MailError::new(format!("failed to set up TLS for {}: {}", config.host, e)))?,
+        };
+        let mut builder = builder.port(config.port).timeout(Some(config.timeout));
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self { transport: builder.build(), from })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), MailError> {
+        let built = build_message(&self.from, message)?;
+        self.transport
+            .send(built)
+            .await
+            .map(|_| ())
+            .map_err(|e| {
+                let error = format!("SMTP delivery to {} failed: {}", message.to, e);
+                // A 5xx reply won't change on a retry
+                if e.is_permanent() {
+                    MailError::permanent(error)
+                } else {
+                    MailError::new(error)
+                }
+            })
+    }
+}
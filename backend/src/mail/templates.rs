@@ -0,0 +1,57 @@
+// src/mail/templates.rs
+
+use crate::mail::{EmailMessage, MailError};
+use serde::Serialize;
+use std::path::Path;
+use tera::{Context, Tera};
+
+pub const DEFAULT_TEMPLATE_DIR: &str = "config/mail";
+
+/// Tera templates for the emails the service sends. Each message `name` has a
+/// `name.subject.txt` and `name.txt` template and optionally `name.html`.
+pub struct MailTemplates {
+    tera: Tera,
+}
+
+impl MailTemplates {
+    /// Loads every template under `dir`. A missing directory yields no templates,
+    /// so rendering fails until some are installed.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Self, MailError> {
+        let dir = dir.as_ref();
+        if !dir.exists() {
+            return Ok(Self { tera: Tera::default() });
+        }
+        let tera = Tera::new(&format!("{}/**/*", dir.display()))
+            .map_err(|e| MailError::new(format!("failed to load mail templates: {}", e)))?;
+        Ok(Self { tera })
+    }
+
+    pub fn template_count(&self) -> usize {
+        self.tera.get_template_names().count()
+    }
+
+    /// Renders message `name` for `to`.
+    pub fn render(&self, name: &str, to: &str, context: &impl Serialize) -> Result<EmailMessage, MailError> {
+        let context = Context::from_serialize(context)
+            .map_err(|e| MailError::new(format!("invalid context for {}: {}", name, e)))?;
+        let render = |file: String| {
+            self.tera.render(&file, &context).map_err(|e| {
+                let cause = std::error::Error::source(&e).map(|cause| cause.to_string());
+                MailError::new(format!("failed to render {}: {}", file, cause.unwrap_or_else(|| e.to_string())))
+            })
+        };
+
+        let html_template = format!("{}.html", name);
+        let html_body = match self.tera.get_template(&html_template) {
+            Ok(_) => Some(render(html_template)?),
+            Err(_) => None,
+        };
+        Ok(EmailMessage {
+            to: to.to_string(),
+            subject: render(format!("{}.subject.txt", name))?.trim().to_string(),
+            text_body: render(format!("{}.txt", name))?,
+            html_body,
+            reply_to: None,
+        })
+    }
+}
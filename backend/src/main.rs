@@ -1,11 +1,11 @@
 // src/main.rs
 
 use axum::http::{header::CONTENT_TYPE, Method};
-use shadow_scan_backend::{app_state::AppState, routes::create_router};
-use sqlx::postgres::PgPoolOptions;
-use std::env;
+use shadow_scan_backend::{
+    app_state::AppState, events::ScanEventHub, evidence, routes::create_router, sms, startup,
+};
+use std::{net::SocketAddr, sync::Arc};
 use tower_http::cors::{Any, CorsLayer};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
 async fn main() {
@@ -13,27 +13,28 @@ async fn main() {
     dotenv::dotenv().ok();
 
     // Initialize tracing (for logging)
-    tracing_subscriber::registry()
-        .with(tracing_subscriber::EnvFilter::new(
-            env::var("RUST_LOG").unwrap_or_else(|_| "shadow_scan_backend=debug,tower_http=debug".into()),
-        ))
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+    startup::init_tracing();
 
     // Database connection pool
-    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    let pool = PgPoolOptions::new()
-        .max_connections(5)
-        .connect(&database_url)
-        .await
-        .expect("Failed to create pool.");
+    let pool = startup::connect_db(5).await;
+
+    // Relay scan progress from Postgres to SSE clients
+    let scan_events = ScanEventHub::new();
+    scan_events.spawn_listener(pool.clone());
 
     // Application state
-    let app_state = AppState { db_pool: pool };
+    let app_state = AppState {
+        db_pool: pool,
+        scan_events,
+        mail_templates: Arc::new(startup::mail_templates()),
+        sms: sms::sms_sender_from_env(),
+        takedown_templates: Arc::new(startup::takedown_templates()),
+        blob_store: evidence::blob_store_from_env(),
+    };
 
     // CORS layer
     let cors = CorsLayer::new()
-        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE, Method::OPTIONS])
         .allow_origin(Any)
         .allow_headers([CONTENT_TYPE]);
 
@@ -43,5 +44,8 @@ async fn main() {
     // Run it
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
     tracing::debug!("listening on {}", listener.local_addr().unwrap());
-    axum::serve(listener, app).await.unwrap();
+    // Peer addresses are recorded with each login's session
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .unwrap();
 }
@@ -0,0 +1,299 @@
+// src/matching/mod.rs
+
+// Decides whether a record found on a people-search site is about the user or
+// about someone else with a similar name.
+
+pub mod nicknames;
+
+use crate::scanner::Identifier;
+use chrono::{Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+pub const DEFAULT_CONFIDENCE_THRESHOLD: f64 = 0.6;
+
+/// Weights of the signals averaged into a record's confidence.
+const NAME_WEIGHT: f64 = 0.5;
+const AGE_WEIGHT: f64 = 0.3;
+const LOCATION_WEIGHT: f64 = 0.2;
+/// Confidence floor once a record lists a phone number, email or street address
+/// of the user's.
+const CONTACT_CONFIDENCE: f64 = 0.95;
+
+/// What is known about the person being scanned for.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MatchProfile {
+    pub identifiers: Vec<Identifier>,
+    pub birth_year: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchSignal {
+    /// `name`, `age`, `location`, `phone`, `email`, `address` or `searched`.
+    pub signal: String,
+    /// How well this signal matched, 0 to 1.
+    pub score: f64,
+    pub explanation: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordMatch {
+    /// How likely the record is about the user, 0 to 1.
+    pub confidence: f64,
+    pub signals: Vec<MatchSignal>,
+}
+
+/// Compares a broker record with the profile. `searched` is the identifier whose
+/// search turned the record up. It is noted among the signals and compared with the
+/// record like the profile's identifiers, but a results page can list anyone, so
+/// being on it counts for nothing by itself.
+pub fn match_record(record: &Map<String, Value>, profile: &MatchProfile, searched: &Identifier) -> RecordMatch {
+    let mut signals = Vec::new();
+    let mut weighted = Vec::new();
+
+    if let Some(signal) = name_signal(record, profile) {
+        weighted.push((NAME_WEIGHT, signal.score));
+        signals.push(signal);
+    }
+    if let Some(signal) = age_signal(record, profile) {
+        weighted.push((AGE_WEIGHT, signal.score));
+        signals.push(signal);
+    }
+    if let Some(signal) = location_signal(record, profile) {
+        weighted.push((LOCATION_WEIGHT, signal.score));
+        signals.push(signal);
+    }
+
+    let total_weight: f64 = weighted.iter().map(|(weight, _)| weight).sum();
+    let mut confidence = if total_weight > 0.0 {
+        weighted.iter().map(|(weight, score)| weight * score).sum::<f64>() / total_weight
+    } else {
+        0.0
+    };
+
+    // Being listed under the search only shows the site returned the record, not that
+    // it is the user's, so only fields the record shares with the user lift it
+    let contact = contact_signals(record, profile, searched);
+    if contact.iter().any(|signal| signal.signal != "searched") {
+        confidence = confidence.max(CONTACT_CONFIDENCE);
+    }
+    signals.extend(contact);
+
+    RecordMatch {
+        confidence: (confidence * 100.0).round() / 100.0,
+        signals,
+    }
+}
+
+/// String values of the record's fields whose names satisfy `wanted`. Array
+/// fields contribute every element.
+fn field_values(record: &Map<String, Value>, wanted: impl Fn(&str) -> bool) -> Vec<String> {
+    record
+        .iter()
+        .filter(|(field, _)| wanted(&field.to_lowercase()))
+        .flat_map(|(_, value)| match value {
+            Value::String(s) => vec![s.clone()],
+            Value::Array(items) => items.iter().filter_map(|i| i.as_str().map(str::to_string)).collect(),
+            _ => Vec::new(),
+        })
+        .filter(|value| !value.trim().is_empty())
+        .collect()
+}
+
+/// Jaro-Winkler similarity rescaled so that only close spellings score: plenty of
+/// unrelated names are 0.75 similar.
+fn similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    ((strsim::jaro_winkler(a, b) - 0.75) / 0.25).clamp(0.0, 1.0)
+}
+
+fn name_tokens(name: &str) -> Vec<String> {
+    name.split(|c: char| !c.is_alphabetic() && c != '\'' && c != '-')
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn name_signal(record: &Map<String, Value>, profile: &MatchProfile) -> Option<MatchSignal> {
+    let record_names = field_values(record, |field| field == "name" || field.ends_with("_name"));
+    let mut best: Option<MatchSignal> = None;
+    for record_name in &record_names {
+        let tokens = name_tokens(record_name);
+        let (Some(first), Some(last)) = (tokens.first(), tokens.last()) else {
+            continue;
+        };
+        for identifier in &profile.identifiers {
+            let Identifier::Name { first_name, last_name, .. } = identifier else {
+                continue;
+            };
+            let (their_first, their_last) = (first_name.to_lowercase(), last_name.to_lowercase());
+            let nickname = first != &their_first && nicknames::are_variants(first, &their_first);
+            let first_score = if nickname { 1.0 } else { similarity(first, &their_first) };
+            let last_score = similarity(last, &their_last);
+            let score = 0.4 * first_score + 0.6 * last_score;
+            if best.as_ref().is_none_or(|b| score > b.score) {
+                let mut explanation = format!(
+                    "\"{}\" compared with \"{} {}\": first name {:.2}, last name {:.2}",
+                    record_name, first_name, last_name, first_score, last_score
+                );
+                if nickname {
+                    explanation.push_str(" (nickname)");
+                }
+                best = Some(MatchSignal { signal: "name".to_string(), score, explanation });
+            }
+        }
+    }
+    best
+}
+
+fn age_signal(record: &Map<String, Value>, profile: &MatchProfile) -> Option<MatchSignal> {
+    let birth_year = profile.birth_year?;
+    let age: i32 = field_values(record, |field| field == "age")
+        .iter()
+        .find_map(|value| {
+            let digits: String = value.chars().skip_while(|c| !c.is_ascii_digit()).take_while(char::is_ascii_digit).collect();
+            digits.parse().ok()
+        })?;
+
+    // Not knowing the birthday, the user is one of two ages this year
+    let expected = Utc::now().year() - birth_year;
+    let off_by = (age - expected).abs().min((age - (expected - 1)).abs());
+    let score = match off_by {
+        0 => 1.0,
+        1..=2 => 0.6,
+        3..=5 => 0.2,
+        _ => 0.0,
+    };
+    Some(MatchSignal {
+        signal: "age".to_string(),
+        score,
+        explanation: format!("listed age {}, expected {} or {}", age, expected - 1, expected),
+    })
+}
+
+fn location_signal(record: &Map<String, Value>, profile: &MatchProfile) -> Option<MatchSignal> {
+    let mut cities = Vec::new();
+    let mut states = Vec::new();
+    for identifier in &profile.identifiers {
+        match identifier {
+            Identifier::Name { city, state, .. } => {
+                cities.extend(city.iter().cloned());
+                states.extend(state.iter().cloned());
+            }
+            Identifier::Address { city, state, .. } => {
+                cities.push(city.clone());
+                states.push(state.clone());
+            }
+            _ => {}
+        }
+    }
+    let addresses = field_values(record, |field| field.contains("address") || field.contains("location"));
+    if addresses.is_empty() || (cities.is_empty() && states.is_empty()) {
+        return None;
+    }
+
+    for address in &addresses {
+        let lower = address.to_lowercase();
+        if let Some(city) = cities.iter().find(|city| lower.contains(&city.to_lowercase())) {
+            return Some(MatchSignal {
+                signal: "location".to_string(),
+                score: 1.0,
+                explanation: format!("\"{}\" is in {}", address, city),
+            });
+        }
+    }
+    for address in &addresses {
+        let tokens = name_tokens(address);
+        if let Some(state) = states.iter().find(|state| tokens.contains(&state.to_lowercase())) {
+            return Some(MatchSignal {
+                signal: "location".to_string(),
+                score: 0.5,
+                explanation: format!("\"{}\" is in the same state, {}", address, state),
+            });
+        }
+    }
+    Some(MatchSignal {
+        signal: "location".to_string(),
+        score: 0.0,
+        explanation: format!("none of {} is in a known city or state", addresses.join("; ")),
+    })
+}
+
+fn digits(value: &str) -> String {
+    let digits: String = value.chars().filter(char::is_ascii_digit).collect();
+    // Compare national numbers, ignoring a leading +1 or other country code
+    digits[digits.len().saturating_sub(10)..].to_string()
+}
+
+fn address_tokens(address: &str) -> Vec<String> {
+    address
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn contact_signals(record: &Map<String, Value>, profile: &MatchProfile, searched: &Identifier) -> Vec<MatchSignal> {
+    let mut signals = Vec::new();
+
+    match searched {
+        Identifier::Email { value } | Identifier::Phone { value } => signals.push(MatchSignal {
+            signal: "searched".to_string(),
+            score: 1.0,
+            explanation: format!("listed under a search for {}", value),
+        }),
+        Identifier::Address { street, .. } => signals.push(MatchSignal {
+            signal: "searched".to_string(),
+            score: 1.0,
+            explanation: format!("listed under a search for {}", street),
+        }),
+        _ => {}
+    }
+
+    let phones = field_values(record, |field| field.contains("phone"));
+    let searched_too = (!profile.identifiers.contains(searched)).then_some(searched);
+    for identifier in profile.identifiers.iter().chain(searched_too) {
+        match identifier {
+            Identifier::Phone { value } => {
+                let theirs = digits(value);
+                if let Some(phone) = phones.iter().find(|phone| digits(phone) == theirs) {
+                    signals.push(MatchSignal {
+                        signal: "phone".to_string(),
+                        score: 1.0,
+                        explanation: format!("lists your phone number {}", phone),
+                    });
+                }
+            }
+            Identifier::Email { value } => {
+                let emails = field_values(record, |field| field.contains("email"));
+                if emails.iter().any(|email| email.trim().eq_ignore_ascii_case(value)) {
+                    signals.push(MatchSignal {
+                        signal: "email".to_string(),
+                        score: 1.0,
+                        explanation: format!("lists your email {}", value),
+                    });
+                }
+            }
+            Identifier::Address { street, .. } => {
+                // The street has to be how the listed address starts, word for word,
+                // so 1 Main St doesn't match a neighbour at 11 Main St
+                let street_tokens = address_tokens(street);
+                let addresses = field_values(record, |field| field.contains("address"));
+                let listed = addresses.iter().find(|address| {
+                    !street_tokens.is_empty() && address_tokens(address).starts_with(&street_tokens)
+                });
+                if let Some(address) = listed {
+                    signals.push(MatchSignal {
+                        signal: "address".to_string(),
+                        score: 1.0,
+                        explanation: format!("lists your address {}", address),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    signals
+}
@@ -0,0 +1,62 @@
+// src/matching/nicknames.rs
+
+/// Common English given names and their short forms. Two names are treated as the
+/// same person's when they share a group.
+const GROUPS: &[&[&str]] = &[
+    &["abigail", "abby", "gail"],
+    &["alexander", "alex", "al", "xander", "sasha"],
+    &["alexandra", "alex", "sandra", "sasha", "lexi"],
+    &["alice", "ally", "allie", "ali"],
+    &["alicia", "allie", "ali", "lisa"],
+    &["andrew", "andy", "drew"],
+    &["anthony", "tony"],
+    &["benjamin", "ben", "benny"],
+    &["catherine", "katherine", "kathryn", "cathy", "kathy", "kate", "katie", "kat"],
+    &["charles", "charlie", "chuck", "chas"],
+    &["christine", "christina", "chris", "chrissy", "tina"],
+    &["christopher", "chris", "topher"],
+    &["daniel", "dan", "danny"],
+    &["david", "dave", "davey"],
+    &["deborah", "debra", "deb", "debbie"],
+    &["donald", "don", "donnie"],
+    &["edward", "ed", "eddie", "ted", "ned"],
+    &["elizabeth", "liz", "lizzie", "beth", "betty", "eliza", "lisa"],
+    &["frederick", "fred", "freddie"],
+    &["gregory", "greg"],
+    &["harold", "hal", "harry"],
+    &["henry", "hank", "harry"],
+    &["james", "jim", "jimmy", "jamie"],
+    &["jennifer", "jen", "jenny"],
+    &["jessica", "jess", "jessie"],
+    &["john", "jack", "johnny", "jon"],
+    &["jonathan", "jon", "jonny", "nathan"],
+    &["joseph", "joe", "joey"],
+    &["kenneth", "ken", "kenny"],
+    &["lawrence", "larry"],
+    &["margaret", "maggie", "meg", "peggy", "marge"],
+    &["matthew", "matt"],
+    &["michael", "mike", "mikey", "mick"],
+    &["nicholas", "nick", "nicky"],
+    &["patricia", "pat", "patty", "trish"],
+    &["patrick", "pat", "paddy"],
+    &["peter", "pete"],
+    &["rebecca", "becky", "becca"],
+    &["richard", "rick", "ricky", "rich", "richie", "dick"],
+    &["robert", "rob", "robbie", "bob", "bobby"],
+    &["ronald", "ron", "ronnie"],
+    &["samantha", "sam", "sammy"],
+    &["samuel", "sam", "sammy"],
+    &["stephen", "steven", "steve"],
+    &["susan", "sue", "suzy", "susie"],
+    &["theodore", "theo", "ted", "teddy"],
+    &["thomas", "tom", "tommy"],
+    &["timothy", "tim", "timmy"],
+    &["victoria", "vicky", "tori"],
+    &["william", "will", "bill", "billy", "liam"],
+    &["zachary", "zach", "zack"],
+];
+
+/// Whether two lowercase given names are variants of each other, e.g. `bob` and `robert`.
+pub fn are_variants(a: &str, b: &str) -> bool {
+    a == b || GROUPS.iter().any(|group| group.contains(&a) && group.contains(&b))
+}
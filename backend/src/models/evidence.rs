@@ -0,0 +1,48 @@
+// src/models/evidence.rs
+
+use crate::evidence::ChainedFields;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A response captured from a source as evidence of a finding. The body is kept
+/// in the blob store under `body_hash`.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct EvidenceSnapshot {
+    pub id: Uuid,
+    pub finding_id: Uuid,
+    /// The scan that captured it, or None for removal checks.
+    pub scan_id: Option<Uuid>,
+    /// 1 for the finding's first snapshot, counting up.
+    pub position: i32,
+    pub url: String,
+    pub http_status: i16,
+    /// Response headers as `[name, value]` pairs, in the order received.
+    pub headers: serde_json::Value,
+    pub content_type: Option<String>,
+    pub body_hash: String,
+    pub body_size: i64,
+    pub captured_at: DateTime<Utc>,
+    /// Chain hash of the finding's previous snapshot.
+    pub prev_hash: Option<String>,
+    pub chain_hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl EvidenceSnapshot {
+    pub fn chained_fields(&self) -> ChainedFields<'_> {
+        ChainedFields {
+            finding_id: self.finding_id,
+            position: self.position,
+            url: &self.url,
+            http_status: self.http_status,
+            headers: &self.headers,
+            content_type: self.content_type.as_deref(),
+            body_hash: &self.body_hash,
+            body_size: self.body_size,
+            captured_at: self.captured_at,
+            prev_hash: self.prev_hash.as_deref(),
+        }
+    }
+}
@@ -11,5 +11,6 @@ pub struct Feedback {
     pub user_id: Option<Uuid>,
     pub message: String,
     pub is_false_positive: bool,
+    pub related_result_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
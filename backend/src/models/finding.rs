@@ -0,0 +1,56 @@
+// src/models/finding.rs
+
+use crate::models::scan::{FindingType, RiskLevel};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "finding_state", rename_all = "snake_case")]
+pub enum FindingState {
+    Open,
+    Acknowledged,
+    RemovalRequested,
+    Resolved,
+    Ignored,
+}
+
+/// One exposure tracked across scans, such as a single broker listing, however
+/// many scans have found it.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TrackedFinding {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub fingerprint: String,
+    pub source: String,
+    pub finding_type: FindingType,
+    pub details: serde_json::Value,
+    pub risk_level: RiskLevel,
+    /// Ids of the risk rules that rated the latest occurrence.
+    pub risk_rules: Vec<String>,
+    pub source_link: Option<String>,
+    pub state: FindingState,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub last_scan_id: Option<Uuid>,
+    /// When a removal check last found the listing gone.
+    pub removal_verified_at: Option<DateTime<Utc>>,
+    /// Whether the listing reappeared after its removal was verified.
+    pub relisted: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct FindingStateChange {
+    pub id: Uuid,
+    pub finding_id: Uuid,
+    pub from_state: Option<FindingState>,
+    pub to_state: FindingState,
+    pub note: Option<String>,
+    /// The user who made the change, or None when a scan made it.
+    pub changed_by: Option<Uuid>,
+    pub changed_at: DateTime<Utc>,
+}
@@ -1,5 +1,17 @@
 // src/models/mod.rs
 
+pub mod evidence;
 pub mod feedback;
+pub mod finding;
+pub mod job;
+pub mod outbox;
+pub mod profile;
+pub mod refresh_token;
 pub mod scan;
+pub mod session;
+pub mod schedule;
+pub mod score;
+pub mod suppression;
+pub mod takedown;
 pub mod user;
+pub mod verification;
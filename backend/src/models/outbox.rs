@@ -0,0 +1,49 @@
+// src/models/outbox.rs
+
+use crate::mail::EmailMessage;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "email_status", rename_all = "snake_case")]
+pub enum EmailStatus {
+    Pending,
+    Sent,
+    /// Delivery was given up after too many failed attempts.
+    Failed,
+}
+
+/// An email in the outbox, with its delivery status.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct QueuedEmail {
+    pub id: Uuid,
+    pub recipient: String,
+    pub reply_to: Option<String>,
+    pub subject: String,
+    #[serde(skip_serializing)]
+    pub text_body: String,
+    #[serde(skip_serializing)]
+    pub html_body: Option<String>,
+    pub status: EmailStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl QueuedEmail {
+    pub fn message(&self) -> EmailMessage {
+        EmailMessage {
+            to: self.recipient.clone(),
+            subject: self.subject.clone(),
+            text_body: self.text_body.clone(),
+            html_body: self.html_body.clone(),
+            reply_to: self.reply_to.clone(),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+// src/models/profile.rs
+
+use crate::scanner::Identifier;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct IdentityProfile {
+    pub user_id: Uuid,
+    pub birth_year: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct ProfileIdentifier {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub identifier: Identifier,
+    /// Set on names the user is also known by, such as a maiden name.
+    pub alias: bool,
+    pub created_at: DateTime<Utc>,
+}
@@ -1,26 +1,145 @@
 // src/models/scan.rs
 
+use crate::scanner::Identifier;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{
+    postgres::{PgHasArrayType, PgTypeInfo},
+    FromRow,
+};
+use std::fmt;
 use uuid::Uuid;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "scan_status", rename_all = "snake_case")]
+pub enum ScanStatus {
+    Pending,
+    InProgress,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl ScanStatus {
+    pub const ALL: [ScanStatus; 5] = [
+        ScanStatus::Pending,
+        ScanStatus::InProgress,
+        ScanStatus::Completed,
+        ScanStatus::Failed,
+        ScanStatus::Cancelled,
+    ];
+
+    /// Statuses a scan may move to from this one. A retried attempt moves an
+    /// in-progress scan to in-progress again.
+    pub fn can_transition_to(self, next: ScanStatus) -> bool {
+        use ScanStatus::*;
+        matches!(
+            (self, next),
+            (Pending, InProgress | Failed | Cancelled)
+                | (InProgress, InProgress | Completed | Failed | Cancelled)
+        )
+    }
+
+    /// Statuses from which a scan may move to `self`.
+    pub fn allowed_predecessors(self) -> Vec<ScanStatus> {
+        ScanStatus::ALL
+            .into_iter()
+            .filter(|from| from.can_transition_to(self))
+            .collect()
+    }
+
+    pub fn is_terminal(self) -> bool {
+        matches!(self, ScanStatus::Completed | ScanStatus::Failed | ScanStatus::Cancelled)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ScanStatus::Pending => "pending",
+            ScanStatus::InProgress => "in_progress",
+            ScanStatus::Completed => "completed",
+            ScanStatus::Failed => "failed",
+            ScanStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl PgHasArrayType for ScanStatus {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_scan_status")
+    }
+}
+
+impl fmt::Display for ScanStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "risk_level", rename_all = "snake_case")]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "finding_type", rename_all = "snake_case")]
+pub enum FindingType {
+    EmailLeak,
+    PasswordLeak,
+    SocialMedia,
+    DataBroker,
+}
+
 #[derive(Debug, FromRow, Serialize, Deserialize)]
 pub struct Scan {
     pub id: Uuid,
     pub user_id: Uuid,
-    pub status: String, // e.g., "pending", "in_progress", "completed", "failed"
+    pub status: ScanStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, FromRow, Serialize, Deserialize)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct ScanResult {
     pub id: Uuid,
     pub scan_id: Uuid,
-    pub finding_type: String, // e.g., "email_leak", "password_leak", "social_media"
+    /// Name of the scanner that produced this result.
+    pub source: String,
+    /// Identifies the same finding across scans, see `findings::fingerprint`.
+    pub fingerprint: String,
+    /// The tracked finding this result is an occurrence of.
+    pub finding_id: Option<Uuid>,
+    pub finding_type: FindingType,
     pub details: serde_json::Value,
-    pub risk_level: String, // e.g., "low", "medium", "high", "critical"
+    pub risk_level: RiskLevel,
+    /// Ids of the risk rules that rated this result.
+    pub risk_rules: Vec<String>,
     pub source_link: Option<String>,
     pub found_at: DateTime<Utc>,
 }
+
+/// One of the identifiers a scan searches for.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScanIdentifier {
+    pub id: Uuid,
+    pub scan_id: Uuid,
+    pub identifier: Identifier,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct ScanSource {
+    pub scan_id: Uuid,
+    pub source: String,
+    pub status: String, // e.g., "pending", "running", "completed", "failed", "cancelled"
+    pub findings_count: i32,
+    pub error: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,155 @@
+// src/models/schedule.rs
+
+use chrono::{DateTime, Duration, Months, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::str::FromStr;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "schedule_frequency", rename_all = "snake_case")]
+pub enum ScheduleFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Cron,
+}
+
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct ScanSchedule {
+    pub user_id: Uuid,
+    pub frequency: ScheduleFrequency,
+    pub cron_expression: Option<String>,
+    pub enabled: bool,
+    pub starts_at: DateTime<Utc>,
+    pub next_run_at: DateTime<Utc>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_scan_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Parses a cron expression, evaluated in UTC. Accepts the standard five fields
+/// (minute hour day month weekday), with weekdays numbered 0-7 from Sunday as in
+/// crontab, as well as the cron crate's six and seven field forms with seconds and
+/// year, whose weekdays are numbered 1-7 from Sunday.
+pub fn parse_cron(expression: &str) -> Result<cron::Schedule, String> {
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    let expression = match fields.as_slice() {
+        [minute, hour, day, month, weekday] => {
+            format!("0 {} {} {} {} {}", minute, hour, day, month, crontab_weekdays(weekday))
+        }
+        _ => fields.join(" "),
+    };
+    cron::Schedule::from_str(&expression).map_err(|e| format!("Invalid cron expression: {}", e))
+}
+
+/// Renumbers a crontab weekday field (0 or 7 is Sunday, 1 Monday) the way the cron
+/// crate numbers them (1 is Sunday, 2 Monday). Names, `*` and `?` are left alone, and
+/// so are steps over every day, which pick the same days either way.
+fn crontab_weekdays(field: &str) -> String {
+    let renumber = |day: u8| if day == 7 { 1 } else { day + 1 };
+    field
+        .split(',')
+        .map(|part| {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (range, step.parse::<u8>().ok()),
+                None => (part, Some(1)),
+            };
+            let bounds = match range.split_once('-') {
+                Some((start, end)) => start.parse::<u8>().ok().zip(end.parse::<u8>().ok()),
+                None => range.parse::<u8>().ok().map(|day| (day, day)),
+            };
+            let (Some((start, end)), Some(step)) = (bounds, step) else {
+                return part.to_string();
+            };
+            if start > end || end > 7 || step == 0 {
+                return part.to_string();
+            }
+            let suffix = part.find('/').map_or("", |at| &part[at..]);
+            match (range.contains('-'), end) {
+                (false, _) if suffix.is_empty() => renumber(start).to_string(),
+                (false, _) => format!("{}{}", renumber(start), suffix),
+                // Sunday at the end of the range wraps round to the start of the week
+                (true, 7) if start < 7 => {
+                    let sunday = if (7 - start) % step == 0 { ",1" } else { "" };
+                    format!("{}-7{}{}", start + 1, suffix, sunday)
+                }
+                (true, _) => format!("{}-{}{}", renumber(start), renumber(end), suffix),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Shortest time allowed between two runs of a cron schedule, so no schedule can
+/// make the service hit third-party brokers more often than this.
+pub const MIN_CRON_INTERVAL: Duration = Duration::days(1);
+/// Upcoming runs compared against `MIN_CRON_INTERVAL`. More than two, so an
+/// expression like `0 0 0,1 * * *` is caught even when its next two runs aren't
+/// the close pair.
+const CRON_RUNS_CHECKED: usize = 16;
+
+/// Parses a cron expression a user wants to schedule scans with, rejecting ones
+/// that run more often than `MIN_CRON_INTERVAL`.
+pub fn validate_cron(expression: &str) -> Result<cron::Schedule, String> {
+    let schedule = parse_cron(expression)?;
+    let runs: Vec<_> = schedule.upcoming(Utc).take(CRON_RUNS_CHECKED).collect();
+    if runs.windows(2).any(|pair| pair[1] - pair[0] < MIN_CRON_INTERVAL) {
+        return Err(format!(
+            "Scheduled scans must be at least {} hours apart",
+            MIN_CRON_INTERVAL.num_hours()
+        ));
+    }
+    Ok(schedule)
+}
+
+/// The first run of a schedule strictly after `after`. Daily, weekly and monthly
+/// schedules run at whole periods from `starts_at`, so they don't drift; runs missed
+/// while nothing was running are skipped rather than caught up.
+pub fn next_run_after(
+    frequency: ScheduleFrequency,
+    cron_expression: Option<&str>,
+    starts_at: DateTime<Utc>,
+    after: DateTime<Utc>,
+) -> Result<DateTime<Utc>, String> {
+    if after < starts_at && frequency != ScheduleFrequency::Cron {
+        return Ok(starts_at);
+    }
+
+    let period = match frequency {
+        ScheduleFrequency::Daily => Duration::days(1),
+        ScheduleFrequency::Weekly => Duration::weeks(1),
+        ScheduleFrequency::Monthly => {
+            // Counted from the anchor so a run on the 31st comes back on the 31st
+            let mut months = 1;
+            loop {
+                let run = starts_at
+                    .checked_add_months(Months::new(months))
+                    .ok_or_else(|| "Schedule is out of range".to_string())?;
+                if run > after {
+                    return Ok(run);
+                }
+                months += 1;
+            }
+        }
+        ScheduleFrequency::Cron => {
+            let schedule = parse_cron(cron_expression.unwrap_or_default())?;
+            let from = after.max(starts_at - Duration::seconds(1));
+            return schedule
+                .after(&from)
+                .next()
+                .ok_or_else(|| "Cron expression never runs again".to_string());
+        }
+    };
+
+    let elapsed = (after - starts_at).num_seconds() / period.num_seconds();
+    Ok(starts_at + period * (elapsed as i32 + 1))
+}
+
+impl ScanSchedule {
+    pub fn next_run_after(&self, after: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+        next_run_after(self.frequency, self.cron_expression.as_deref(), self.starts_at, after)
+    }
+}
@@ -0,0 +1,15 @@
+// src/models/score.rs
+
+use crate::findings::score::ScoreFactor;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScanScore {
+    pub scan_id: Uuid,
+    pub user_id: Uuid,
+    pub score: i32,
+    pub breakdown: Vec<ScoreFactor>,
+    pub computed_at: DateTime<Utc>,
+}
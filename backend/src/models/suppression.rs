@@ -0,0 +1,31 @@
+// src/models/suppression.rs
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A fingerprint the user reported as a false positive. Later scans skip
+/// results that match it.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Suppression {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub fingerprint: String,
+    pub source: String,
+    pub finding_id: Option<Uuid>,
+    pub feedback_id: Option<Uuid>,
+    /// Whether the report is what moved the finding to ignored.
+    pub ignored_finding: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// How often results from a source, or rated by a risk rule, turn out to be
+/// false positives, counted over one user's distinct findings.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PrecisionStat {
+    pub key: String,
+    pub findings: i64,
+    pub false_positives: i64,
+    pub precision: f64,
+}
@@ -0,0 +1,118 @@
+// src/models/takedown.rs
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "takedown_channel", rename_all = "snake_case")]
+pub enum TakedownChannel {
+    Email,
+    WebForm,
+    Postal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "takedown_status", rename_all = "snake_case")]
+pub enum TakedownStatus {
+    Sent,
+    Acknowledged,
+    Extended,
+    Completed,
+    Refused,
+    /// The deadline passed without the broker completing the request.
+    Overdue,
+}
+
+impl TakedownStatus {
+    /// Whether the broker still owes an answer.
+    pub fn is_pending(self) -> bool {
+        matches!(self, TakedownStatus::Sent | TakedownStatus::Acknowledged | TakedownStatus::Extended)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "takedown_response_kind", rename_all = "snake_case")]
+pub enum TakedownResponseKind {
+    Acknowledged,
+    /// The broker extended the deadline, as the law allows once.
+    Extension,
+    Completed,
+    Refused,
+    Other,
+}
+
+/// A removal request sent to a data broker about one finding.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TakedownRequest {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub finding_id: Uuid,
+    pub broker: String,
+    pub jurisdiction: String,
+    pub channel: TakedownChannel,
+    pub status: TakedownStatus,
+    pub subject: String,
+    pub body: String,
+    pub sent_at: DateTime<Utc>,
+    pub extension_days: i32,
+    pub deadline_at: DateTime<Utc>,
+    pub overdue_at: Option<DateTime<Utc>>,
+    /// The queued email, when the service sent the letter itself.
+    pub email_id: Option<Uuid>,
+    /// When the broker is next re-scanned to verify the removal; None once
+    /// monitoring stopped.
+    pub next_check_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TakedownResponse {
+    pub id: Uuid,
+    pub request_id: Uuid,
+    pub kind: TakedownResponseKind,
+    pub note: Option<String>,
+    pub received_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "removal_check_outcome", rename_all = "snake_case")]
+pub enum RemovalCheckOutcome {
+    /// The broker no longer lists the finding.
+    Removed,
+    Listed,
+    /// The broker could not be searched.
+    Failed,
+}
+
+/// One re-scan of the broker a takedown request went to.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct RemovalCheck {
+    pub id: Uuid,
+    pub request_id: Uuid,
+    pub finding_id: Uuid,
+    pub outcome: RemovalCheckOutcome,
+    pub evidence: serde_json::Value,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// A complaint to the supervisory authority, drafted when a request went overdue.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TakedownEscalation {
+    pub id: Uuid,
+    pub request_id: Uuid,
+    pub template_set: String,
+    pub subject: String,
+    pub text_body: String,
+    pub html_body: String,
+    /// Evidence snapshots the letter cites.
+    pub evidence_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
@@ -11,6 +11,10 @@ pub struct User {
     pub username: String,
     pub email: String,
     pub password_hash: String,
+    /// Unset until the user follows the link sent to `email`; unverified
+    /// accounts can't scan.
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub verification_sent_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
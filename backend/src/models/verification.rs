@@ -0,0 +1,30 @@
+// src/models/verification.rs
+
+use crate::scanner::IdentifierKind;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct IdentifierVerification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: IdentifierKind,
+    pub value: String,
+    #[serde(skip_serializing)]
+    pub code_hash: String,
+    pub attempts: i32,
+    pub expires_at: DateTime<Utc>,
+    pub consumed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct VerifiedIdentifier {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: IdentifierKind,
+    pub value: String,
+    pub verified_at: DateTime<Utc>,
+}
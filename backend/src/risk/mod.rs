@@ -0,0 +1,156 @@
+// src/risk/mod.rs
+
+// Declarative rules assigning risk levels to findings. Rules live in a TOML file so
+// how findings are rated can be tuned without a code change.
+
+use crate::{
+    models::scan::{FindingType, RiskLevel},
+    scanner::Finding,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+pub const DEFAULT_RULES_FILE: &str = "config/risk_rules.toml";
+
+/// A rule matches a finding when every condition it sets holds. Detail paths are
+/// dot separated, e.g. `record.home_address`, and count as present when they hold
+/// anything other than null or an empty string, array or object.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RiskRule {
+    /// Stable identifier, stored on the results the rule matched.
+    pub id: String,
+    pub description: String,
+    pub risk_level: RiskLevel,
+    /// Finding types the rule applies to; any type if empty.
+    #[serde(default)]
+    pub finding_types: Vec<FindingType>,
+    /// Scanner names the rule applies to; any source if empty.
+    #[serde(default)]
+    pub sources: Vec<String>,
+    /// Detail paths that must all be present.
+    #[serde(default)]
+    pub all: Vec<String>,
+    /// Detail paths of which at least one must be present.
+    #[serde(default)]
+    pub any: Vec<String>,
+    /// Detail paths that must all be absent.
+    #[serde(default)]
+    pub none: Vec<String>,
+    /// Detail paths that must hold these strings, compared case-insensitively.
+    #[serde(default)]
+    pub equals: BTreeMap<String, String>,
+}
+
+#[derive(Debug)]
+pub enum RuleError {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, toml::de::Error),
+    Invalid(String, String),
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::Io(path, e) => write!(f, "failed to read {}: {}", path.display(), e),
+            RuleError::Parse(path, e) => write!(f, "failed to parse {}: {}", path.display(), e),
+            RuleError::Invalid(rule, msg) => write!(f, "invalid risk rule {}: {}", rule, msg),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RiskRules {
+    #[serde(default)]
+    pub rules: Vec<RiskRule>,
+}
+
+impl RiskRules {
+    /// Loads rules from a TOML file of `[[rules]]` tables. A missing file yields no
+    /// rules, leaving findings at the risk level their scanner gave them.
+    pub fn load_file(path: impl AsRef<Path>) -> Result<Self, RuleError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path).map_err(|e| RuleError::Io(path.to_path_buf(), e))?;
+        Self::from_toml(&contents).map_err(|e| match e {
+            RuleError::Parse(_, e) => RuleError::Parse(path.to_path_buf(), e),
+            other => other,
+        })
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, RuleError> {
+        let rules: RiskRules = toml::from_str(contents).map_err(|e| RuleError::Parse(PathBuf::new(), e))?;
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    fn validate(&self) -> Result<(), RuleError> {
+        let mut seen = HashSet::new();
+        for rule in &self.rules {
+            if !seen.insert(rule.id.as_str()) {
+                return Err(RuleError::Invalid(rule.id.clone(), "duplicate rule id".to_string()));
+            }
+            let unconditional = rule.finding_types.is_empty()
+                && rule.sources.is_empty()
+                && rule.all.is_empty()
+                && rule.any.is_empty()
+                && rule.none.is_empty()
+                && rule.equals.is_empty();
+            if unconditional {
+                return Err(RuleError::Invalid(rule.id.clone(), "no conditions defined".to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rates a finding from `source`: the highest risk level among the rules it
+    /// matches, or the scanner's own level if none match. Also returns the ids of
+    /// the matched rules.
+    pub fn evaluate(&self, source: &str, finding: &Finding) -> (RiskLevel, Vec<String>) {
+        let matched: Vec<&RiskRule> = self.rules.iter().filter(|rule| rule.matches(source, finding)).collect();
+        let risk_level = matched
+            .iter()
+            .map(|rule| rule.risk_level)
+            .max()
+            .unwrap_or(finding.risk_level);
+        (risk_level, matched.into_iter().map(|rule| rule.id.clone()).collect())
+    }
+}
+
+impl RiskRule {
+    fn matches(&self, source: &str, finding: &Finding) -> bool {
+        let details = &finding.details;
+        (self.finding_types.is_empty() || self.finding_types.contains(&finding.finding_type))
+            && (self.sources.is_empty() || self.sources.iter().any(|s| s == source))
+            && self.all.iter().all(|path| is_present(lookup(details, path)))
+            && (self.any.is_empty() || self.any.iter().any(|path| is_present(lookup(details, path))))
+            && !self.none.iter().any(|path| is_present(lookup(details, path)))
+            && self.equals.iter().all(|(path, expected)| {
+                lookup(details, path)
+                    .and_then(Value::as_str)
+                    .is_some_and(|actual| actual.trim().eq_ignore_ascii_case(expected))
+            })
+    }
+}
+
+fn lookup<'a>(details: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(details, |value, key| value.get(key))
+}
+
+fn is_present(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::String(s)) => !s.trim().is_empty(),
+        Some(Value::Array(items)) => !items.is_empty(),
+        Some(Value::Object(map)) => !map.is_empty(),
+        Some(_) => true,
+    }
+}
@@ -3,11 +3,14 @@
 use crate::{
     app_state::AppState,
     auth,
-    handlers::{feedback, health, scan},
+    handlers::{
+        evidence, feedback, finding, health, identifier, profile, scan, schedule, score, session, suppression,
+        takedown,
+    },
 };
 use axum::{
     middleware,
-    routing::{get, post},
+    routing::{delete, get, post},
     Router,
 };
 
@@ -16,6 +19,42 @@ pub fn create_router(app_state: AppState) -> Router {
     let protected_routes = Router::new()
         .route("/api/scan", post(scan::start_scan))
         .route("/api/results/:user_id", get(scan::get_scan_results))
+        .route("/api/scans/:scan_id/events", get(scan::scan_events))
+        .route("/api/scans/:scan_id/cancel", post(scan::cancel_scan))
+        .route("/api/scans/:scan_id/diff", get(scan::scan_diff))
+        .route("/api/score/history", get(score::score_history))
+        .route("/api/findings", get(finding::list_findings))
+        .route("/api/findings/:finding_id", get(finding::get_finding))
+        .route("/api/findings/:finding_id/state", post(finding::update_finding_state))
+        .route("/api/findings/:finding_id/takedown", post(takedown::generate_takedown))
+        .route("/api/findings/:finding_id/evidence", get(evidence::list_evidence))
+        .route("/api/findings/:finding_id/evidence/:snapshot_id", get(evidence::get_evidence_body))
+        .route(
+            "/api/profile",
+            get(profile::get_profile).put(profile::save_profile).delete(profile::delete_profile),
+        )
+        .route("/api/profile/identifiers", post(profile::add_identifier))
+        .route("/api/profile/identifiers/:id", delete(profile::remove_identifier))
+        .route(
+            "/api/schedule",
+            get(schedule::get_schedule).put(schedule::save_schedule).delete(schedule::delete_schedule),
+        )
+        .route(
+            "/api/takedowns",
+            get(takedown::list_takedown_requests).post(takedown::create_takedown_request),
+        )
+        .route("/api/takedowns/:request_id", get(takedown::get_takedown_request))
+        .route("/api/takedowns/:request_id/responses", post(takedown::add_takedown_response))
+        .route("/api/suppressions", get(suppression::list_suppressions))
+        .route("/api/suppressions/:suppression_id", delete(suppression::remove_suppression))
+        .route("/api/stats/precision", get(suppression::precision_stats))
+        .route("/api/identifiers", get(identifier::list_verified))
+        .route("/api/identifiers/:id", delete(identifier::remove_verified))
+        .route("/api/identifiers/verify", post(identifier::request_verification))
+        .route("/api/identifiers/verify/confirm", post(identifier::confirm_verification))
+        .route("/api/verify-email/resend", post(auth::handler::resend_verification))
+        .route("/api/sessions", get(session::list_sessions).delete(session::revoke_other_sessions))
+        .route("/api/sessions/:session_id", delete(session::revoke_session))
         .route_layer(middleware::from_fn_with_state(
             app_state.clone(),
             auth::middleware::auth,
@@ -26,7 +65,17 @@ pub fn create_router(app_state: AppState) -> Router {
         .route("/api/health", get(health::health_check))
         .route("/api/register", post(auth::handler::register))
         .route("/api/login", post(auth::handler::login))
-        .route("/api/feedback", post(feedback::submit_feedback))
+        .route("/api/token/refresh", post(auth::handler::refresh_token))
+        .route("/api/logout", post(auth::handler::logout))
+        .route("/api/verify-email", get(auth::handler::verify_email))
+        .route(
+            "/api/feedback",
+            post(feedback::submit_feedback).route_layer(middleware::from_fn_with_state(
+                app_state.clone(),
+                auth::middleware::optional_auth,
+            )),
+        )
+        .route("/api/identifiers/verify/:verification_id", get(identifier::verify_link))
         .merge(protected_routes)
         .with_state(app_state)
 }
@@ -0,0 +1,41 @@
+// src/scanner/breach_db.rs
+
+use crate::{
+    models::scan::{FindingType, RiskLevel},
+    scanner::{Finding, IdentifierKind, ScanTarget, Scanner, ScannerError},
+};
+use async_trait::async_trait;
+use serde_json::json;
+use tokio_util::sync::CancellationToken;
+
+/// Simulated breach database lookup. Reports every email as leaked.
+pub struct BreachDbScanner;
+
+#[async_trait]
+impl Scanner for BreachDbScanner {
+    fn name(&self) -> &str {
+        "breach_db"
+    }
+
+    fn supported_kinds(&self) -> &[IdentifierKind] {
+        &[IdentifierKind::Email]
+    }
+
+    async fn scan(
+        &self,
+        target: &ScanTarget,
+        _cancel: &CancellationToken,
+    ) -> Result<Vec<Finding>, ScannerError> {
+        let findings = target
+            .emails()
+            .map(|email| Finding {
+                finding_type: FindingType::EmailLeak,
+                details: json!({ "source": "Simulated Breach DB", "leaked_email": email }),
+                risk_level: RiskLevel::High,
+                source_link: Some("https://haveibeenpwned.com/".to_string()),
+                capture: None,
+            })
+            .collect();
+        Ok(findings)
+    }
+}
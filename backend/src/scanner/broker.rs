@@ -0,0 +1,260 @@
+// src/scanner/broker.rs
+
+use crate::{
+    brokers::{extract, Broker, BrokerSearch},
+    evidence::Capture,
+    matching::{self, MatchProfile},
+    models::scan::FindingType,
+    scanner::{Finding, Identifier, IdentifierKind, ScanTarget, Scanner, ScannerError},
+};
+use async_trait::async_trait;
+use reqwest::{StatusCode, Url};
+use serde_json::{json, Map, Value};
+use tokio_util::sync::CancellationToken;
+
+/// A broker's results page for one identifier.
+#[derive(Debug)]
+pub struct SearchPage {
+    pub searched: Identifier,
+    pub capture: Capture,
+    /// Every record read from the page, whether or not it matches the user.
+    pub records: Vec<ListedRecord>,
+    /// The page carries the broker's `no_results_text`.
+    pub shows_no_results: bool,
+}
+
+impl SearchPage {
+    /// Whether the broker answered with a 2xx status.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.capture.status)
+    }
+
+    /// Whether the broker said nothing matched the search, with a 404 or its
+    /// no-results page, as opposed to a page with no records that could be read.
+    pub fn found_nothing(&self) -> bool {
+        self.records.is_empty() && (self.capture.status == StatusCode::NOT_FOUND.as_u16() || self.shows_no_results)
+    }
+}
+
+/// A record on a results page.
+#[derive(Debug)]
+pub struct ListedRecord {
+    pub record: Map<String, Value>,
+    /// The record's own page, which identifies the listing across scans.
+    pub listing_url: Option<Url>,
+}
+
+/// Searches a single data broker from the catalog and reports the records on its
+/// results page that are likely about the user as `data_broker` findings.
+pub struct BrokerScanner {
+    broker: Broker,
+    kinds: Vec<IdentifierKind>,
+    client: reqwest::Client,
+    match_threshold: f64,
+}
+
+impl BrokerScanner {
+    pub fn new(broker: Broker, client: reqwest::Client, match_threshold: f64) -> Self {
+        let kinds = broker.searches.iter().map(|search| search.kind).collect();
+        Self { broker, kinds, client, match_threshold }
+    }
+
+    /// Fetches the broker's results page for each identifier in `target` it can
+    /// search for, stopping early once `cancel` is cancelled.
+    pub async fn search_pages(
+        &self,
+        target: &ScanTarget,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<SearchPage>, ScannerError> {
+        let mut pages = Vec::new();
+        for identifier in &target.identifiers {
+            if cancel.is_cancelled() {
+                break;
+            }
+            if let Some(search) = self.broker.search_for(identifier.kind()) {
+                pages.push(self.fetch_page(search, identifier).await?);
+            }
+        }
+        Ok(pages)
+    }
+
+    async fn fetch_page(&self, search: &BrokerSearch, identifier: &Identifier) -> Result<SearchPage, ScannerError> {
+        let url = build_search_url(search, identifier)?;
+
+        let response = self
+            .client
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(|e| ScannerError(format!("request to {} failed: {}", self.broker.id, e)))?;
+        // Most brokers answer "no results" with a 404
+        let no_results = response.status() == StatusCode::NOT_FOUND;
+        let response = if no_results {
+            response
+        } else {
+            response
+                .error_for_status()
+                .map_err(|e| ScannerError(format!("{} returned an error: {}", self.broker.id, e)))?
+        };
+        let status = response.status().as_u16();
+        let headers: Vec<(String, String)> = response
+            .headers()
+            .iter()
+            .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned()))
+            .collect();
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| ScannerError(format!("failed to read {} response: {}", self.broker.id, e)))?;
+        let capture = Capture {
+            url: url.to_string(),
+            status,
+            headers,
+            content_type,
+            body: body.to_vec().into(),
+            captured_at: chrono::Utc::now(),
+        };
+
+        let (records, shows_no_results) = if no_results {
+            (Vec::new(), false)
+        } else {
+            let html = String::from_utf8_lossy(&capture.body);
+            let records = extract::extract_records(&self.broker.extract, &html).map_err(ScannerError)?;
+            (records, extract::shows_no_results(&self.broker.extract, &html))
+        };
+        let records = records
+            .into_iter()
+            .map(|record| {
+                let listing_url = self
+                    .broker
+                    .extract
+                    .link_field
+                    .as_ref()
+                    .and_then(|field| record.get(field))
+                    .and_then(|link| link.as_str())
+                    .and_then(|link| url.join(link).ok());
+                ListedRecord { record, listing_url }
+            })
+            .collect();
+        Ok(SearchPage { searched: identifier.clone(), capture, records, shows_no_results })
+    }
+
+    /// The records on `page` likely about the user, as findings.
+    fn findings(&self, page: &SearchPage, profile: &MatchProfile) -> Vec<Finding> {
+        page.records
+            .iter()
+            .filter_map(|listed| {
+                let matched = matching::match_record(&listed.record, profile, &page.searched);
+                if matched.confidence < self.match_threshold {
+                    tracing::debug!(
+                        "Dropping {} record with match confidence {:.2}",
+                        self.broker.id,
+                        matched.confidence
+                    );
+                    return None;
+                }
+                let source_link = listed.listing_url.as_ref().map_or_else(|| page.capture.url.clone(), Url::to_string);
+                Some(Finding {
+                    finding_type: FindingType::DataBroker,
+                    details: json!({
+                        "broker": self.broker.id,
+                        "broker_name": self.broker.name,
+                        "searched": page.searched,
+                        "record": listed.record,
+                        "listing_url": listed.listing_url.as_ref().map(Url::to_string),
+                        "match": matched,
+                        "opt_out_url": self.broker.opt_out_url,
+                        "jurisdiction": self.broker.jurisdiction,
+                        "contact_email": self.broker.contact_email,
+                    }),
+                    risk_level: self.broker.risk_level,
+                    source_link: Some(source_link),
+                    capture: Some(page.capture.clone()),
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Scanner for BrokerScanner {
+    fn name(&self) -> &str {
+        self.broker.id.as_str()
+    }
+
+    fn supported_kinds(&self) -> &[IdentifierKind] {
+        &self.kinds
+    }
+
+    async fn scan(
+        &self,
+        target: &ScanTarget,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<Finding>, ScannerError> {
+        let pages = self.search_pages(target, cancel).await?;
+        let findings = pages.iter().flat_map(|page| self.findings(page, &target.profile)).collect();
+        Ok(findings)
+    }
+}
+
+/// The broker's search page for `identifier`, with the identifier filled into the
+/// URL template and query parameters.
+pub fn build_search_url(search: &BrokerSearch, identifier: &Identifier) -> Result<Url, ScannerError> {
+    let fields = identifier.template_fields();
+
+    let mut url = Url::parse(&fill_template(&search.url_template, &fields, true))
+        .map_err(|e| ScannerError(format!("invalid search url: {}", e)))?;
+    if !search.query.is_empty() {
+        let mut params: Vec<_> = search.query.iter().collect();
+        params.sort();
+        let mut pairs = url.query_pairs_mut();
+        for (name, value) in params {
+            pairs.append_pair(name, &fill_template(value, &fields, false));
+        }
+    }
+    Ok(url)
+}
+
+/// Replaces `{name}` placeholders, percent-encoding the values when they go into a URL path.
+/// The template is read in a single pass, so braces inside a substituted value are
+/// never taken for placeholders. Unknown placeholders are left as they are.
+fn fill_template(template: &str, fields: &[(&str, String)], encode: bool) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let field = after
+            .find('}')
+            .and_then(|end| fields.iter().find(|(name, _)| *name == &after[..end]).map(|field| (end, field)));
+        match field {
+            Some((end, (_, value))) => {
+                filled.push_str(&if encode { percent_encode(value) } else { value.clone() });
+                rest = &after[end + 1..];
+            }
+            None => {
+                filled.push('{');
+                rest = after;
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'@' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
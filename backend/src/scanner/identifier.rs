@@ -0,0 +1,182 @@
+// src/scanner/identifier.rs
+
+use serde::{Deserialize, Serialize};
+use validator::ValidateEmail;
+
+/// The kinds of personal identifiers a scan can be run against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "identifier_kind", rename_all = "snake_case")]
+pub enum IdentifierKind {
+    Email,
+    Phone,
+    Username,
+    Name,
+    Address,
+}
+
+/// A single piece of personal information to look for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Identifier {
+    Email {
+        value: String,
+    },
+    /// Stored in E.164 form, e.g. `+15551234567`.
+    Phone {
+        value: String,
+    },
+    Username {
+        value: String,
+    },
+    /// A full name, optionally narrowed down to where the person lives.
+    Name {
+        first_name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        middle_name: Option<String>,
+        last_name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        city: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        state: Option<String>,
+    },
+    /// A current or past postal address.
+    Address {
+        street: String,
+        city: String,
+        state: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        postal_code: Option<String>,
+    },
+}
+
+impl Identifier {
+    pub fn kind(&self) -> IdentifierKind {
+        match self {
+            Identifier::Email { .. } => IdentifierKind::Email,
+            Identifier::Phone { .. } => IdentifierKind::Phone,
+            Identifier::Username { .. } => IdentifierKind::Username,
+            Identifier::Name { .. } => IdentifierKind::Name,
+            Identifier::Address { .. } => IdentifierKind::Address,
+        }
+    }
+
+    /// The value a user must prove they own before it can be scanned. Only emails
+    /// and phone numbers can be verified; other identifiers return None.
+    pub fn verifiable_value(&self) -> Option<&str> {
+        match self {
+            Identifier::Email { value } | Identifier::Phone { value } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Validates the identifier and returns it in canonical form, so the same
+    /// identifier typed differently is stored and searched identically.
+    pub fn normalized(&self) -> Result<Identifier, String> {
+        match self {
+            Identifier::Email { value } => {
+                let value = value.trim().to_lowercase();
+                if !value.validate_email() {
+                    return Err(format!("Invalid email address: {}", value));
+                }
+                Ok(Identifier::Email { value })
+            }
+            Identifier::Phone { value } => Ok(Identifier::Phone {
+                value: normalize_phone(value).ok_or_else(|| format!("Invalid phone number: {}", value))?,
+            }),
+            Identifier::Username { value } => {
+                let value = value.trim().trim_start_matches('@').to_string();
+                let valid_chars = value
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+                if value.is_empty() || value.len() > 64 || !valid_chars {
+                    return Err(format!("Invalid username: {}", value));
+                }
+                Ok(Identifier::Username { value })
+            }
+            Identifier::Name { first_name, middle_name, last_name, city, state } => Ok(Identifier::Name {
+                first_name: required_name_part("First name", first_name)?,
+                middle_name: optional(middle_name),
+                last_name: required_name_part("Last name", last_name)?,
+                city: optional(city),
+                state: optional(state).map(|state| state.to_uppercase()),
+            }),
+            Identifier::Address { street, city, state, postal_code } => Ok(Identifier::Address {
+                street: required("Street", street)?,
+                city: required("City", city)?,
+                state: required("State", state)?.to_uppercase(),
+                postal_code: optional(postal_code),
+            }),
+        }
+    }
+
+    /// Named values that can be substituted into search URL templates.
+    /// Missing optional parts are substituted as empty strings.
+    pub fn template_fields(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Identifier::Email { value } => vec![("email", value.clone())],
+            Identifier::Phone { value } => vec![
+                ("phone", value.trim_start_matches('+').to_string()),
+                ("phone_e164", value.clone()),
+                // National number without the country code, for US-centric sites
+                ("phone_national", value.strip_prefix("+1").unwrap_or(value).to_string()),
+            ],
+            Identifier::Username { value } => vec![("username", value.clone())],
+            Identifier::Name { first_name, middle_name, last_name, city, state } => vec![
+                ("first_name", first_name.clone()),
+                ("middle_name", middle_name.clone().unwrap_or_default()),
+                ("last_name", last_name.clone()),
+                ("full_name", format!("{} {}", first_name, last_name)),
+                ("city", city.clone().unwrap_or_default()),
+                ("state", state.clone().unwrap_or_default()),
+            ],
+            Identifier::Address { street, city, state, postal_code } => vec![
+                ("street", street.clone()),
+                ("city", city.clone()),
+                ("state", state.clone()),
+                ("postal_code", postal_code.clone().unwrap_or_default()),
+            ],
+        }
+    }
+}
+
+/// Accepts common phone formats and returns E.164. Ten-digit numbers are assumed
+/// to be North American.
+fn normalize_phone(value: &str) -> Option<String> {
+    let value = value.trim();
+    let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
+    let has_other = value
+        .chars()
+        .any(|c| !c.is_ascii_digit() && !matches!(c, '+' | ' ' | '-' | '.' | '(' | ')'));
+    if has_other {
+        return None;
+    }
+    match digits.len() {
+        10 if !value.starts_with('+') => Some(format!("+1{}", digits)),
+        11..=15 => Some(format!("+{}", digits)),
+        _ => None,
+    }
+}
+
+fn required(field: &str, value: &str) -> Result<String, String> {
+    let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    if value.is_empty() {
+        return Err(format!("{} is required", field));
+    }
+    Ok(value)
+}
+
+fn required_name_part(field: &str, value: &str) -> Result<String, String> {
+    let value = required(field, value)?;
+    if !value.chars().all(|c| c.is_alphabetic() || matches!(c, ' ' | '-' | '\'' | '.')) {
+        return Err(format!("{} contains invalid characters", field));
+    }
+    Ok(value)
+}
+
+fn optional(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(|value| value.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|value| !value.is_empty())
+}
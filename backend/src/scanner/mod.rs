@@ -0,0 +1,150 @@
+// src/scanner/mod.rs
+
+pub mod breach_db;
+pub mod broker;
+pub mod identifier;
+pub mod runner;
+pub mod social_media;
+
+use crate::{
+    brokers::BrokerCatalog,
+    evidence::Capture,
+    matching::MatchProfile,
+    models::scan::{FindingType, RiskLevel},
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::{fmt, sync::Arc};
+use tokio_util::sync::CancellationToken;
+
+pub use identifier::{Identifier, IdentifierKind};
+
+/// Everything a scanner needs to know about who it is looking for.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanTarget {
+    pub identifiers: Vec<Identifier>,
+    /// Everything known about the person, used to tell their records apart from
+    /// namesakes'. Kept whole by `restricted_to`.
+    #[serde(default)]
+    pub profile: MatchProfile,
+}
+
+impl ScanTarget {
+    /// Returns a copy of the target containing only the given identifier kinds.
+    pub fn restricted_to(&self, kinds: &[IdentifierKind]) -> ScanTarget {
+        ScanTarget {
+            identifiers: self
+                .identifiers
+                .iter()
+                .filter(|identifier| kinds.contains(&identifier.kind()))
+                .cloned()
+                .collect(),
+            profile: self.profile.clone(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.identifiers.is_empty()
+    }
+
+    pub fn emails(&self) -> impl Iterator<Item = &str> {
+        self.identifiers.iter().filter_map(|identifier| match identifier {
+            Identifier::Email { value } => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn usernames(&self) -> impl Iterator<Item = &str> {
+        self.identifiers.iter().filter_map(|identifier| match identifier {
+            Identifier::Username { value } => Some(value.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/// A single piece of exposed information reported by a scanner, before it is persisted.
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub finding_type: FindingType,
+    pub details: serde_json::Value,
+    pub risk_level: RiskLevel,
+    pub source_link: Option<String>,
+    /// The response the finding was read from, kept as evidence.
+    pub capture: Option<Capture>,
+}
+
+#[derive(Debug)]
+pub struct ScannerError(pub String);
+
+impl fmt::Display for ScannerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ScannerError {}
+
+/// A source of findings, e.g. a breach database or a data broker site.
+#[async_trait]
+pub trait Scanner: Send + Sync {
+    /// Stable, unique name of the source, used in logs and results.
+    fn name(&self) -> &str;
+
+    /// Identifier kinds this source knows how to search for. The target passed
+    /// to `scan` only ever contains identifiers of these kinds.
+    fn supported_kinds(&self) -> &[IdentifierKind];
+
+    /// Searches the source. Long-running scanners should check `cancel` between
+    /// requests and return early once it is cancelled; whatever they return after
+    /// cancellation is discarded.
+    async fn scan(
+        &self,
+        target: &ScanTarget,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<Finding>, ScannerError>;
+}
+
+/// The set of scanners a scan fans out to.
+#[derive(Clone, Default)]
+pub struct ScannerRegistry {
+    scanners: Vec<Arc<dyn Scanner>>,
+    /// The broker scanners among `scanners`, for removal checks that need their pages.
+    brokers: Vec<Arc<broker::BrokerScanner>>,
+}
+
+impl ScannerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry containing every built-in source.
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register(breach_db::BreachDbScanner);
+        registry.register(social_media::SocialMediaScanner);
+        registry
+    }
+
+    /// Registers a scanner per broker. Records matching the user with less than
+    /// `match_threshold` confidence are dropped.
+    pub fn register_brokers(&mut self, catalog: &BrokerCatalog, client: &reqwest::Client, match_threshold: f64) {
+        for broker in &catalog.brokers {
+            let scanner = Arc::new(broker::BrokerScanner::new(broker.clone(), client.clone(), match_threshold));
+            self.scanners.push(scanner.clone());
+            self.brokers.push(scanner);
+        }
+    }
+
+    pub fn register(&mut self, scanner: impl Scanner + 'static) {
+        self.scanners.push(Arc::new(scanner));
+    }
+
+    pub fn scanners(&self) -> &[Arc<dyn Scanner>] {
+        &self.scanners
+    }
+
+    /// The registered scanner for the broker with catalog id `id`.
+    pub fn broker(&self, id: &str) -> Option<&broker::BrokerScanner> {
+        self.brokers.iter().find(|scanner| scanner.name() == id).map(Arc::as_ref)
+    }
+}
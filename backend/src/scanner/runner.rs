@@ -0,0 +1,258 @@
+// src/scanner/runner.rs
+
+use crate::{
+    db::{
+        scan_repo::{self, StatusUpdateError},
+        score_repo, suppression_repo,
+    },
+    events::{self, ScanNotification, SourceState},
+    evidence::{self, BlobStore},
+    findings::{
+        fingerprint,
+        score::{self, PrivacyScore},
+    },
+    models::scan::ScanStatus,
+    risk::RiskRules,
+    scanner::{ScanTarget, ScannerRegistry},
+};
+use sqlx::{Acquire, PgPool, Postgres, Transaction};
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanOutcome {
+    Completed,
+    Cancelled,
+}
+
+/// Runs every registered source that supports at least one of the target's
+/// identifiers and persists their findings, rated by `risk_rules`, except those
+/// the user suppressed. Responses the findings were read from go to `blob_store`
+/// as evidence. The scan is marked completed unless no source managed to
+/// finish, in which case an error is returned so the attempt can be retried.
+///
+/// When `cancel` fires, sources still running are stopped and, if the scan was
+/// cancelled, marked cancelled; findings already stored are kept.
+pub async fn run_scan(
+    pool: &PgPool,
+    scanners: &ScannerRegistry,
+    risk_rules: &RiskRules,
+    blob_store: &dyn BlobStore,
+    scan_id: Uuid,
+    target: ScanTarget,
+    cancel: CancellationToken,
+) -> Result<ScanOutcome, String> {
+    tracing::info!("Starting scan for scan_id: {}", scan_id);
+
+    match scan_repo::update_scan_status(pool, scan_id, ScanStatus::InProgress).await {
+        Ok(_) => {}
+        Err(StatusUpdateError::IllegalTransition { from: ScanStatus::Cancelled, .. }) => {
+            tracing::info!("Scan {} was cancelled before it started", scan_id);
+            return Ok(ScanOutcome::Cancelled);
+        }
+        Err(e) => return Err(format!("failed to start scan: {}", e)),
+    }
+    scan_repo::delete_scan_results(pool, scan_id)
+        .await
+        .map_err(|e| format!("failed to clear previous results: {}", e))?;
+    let suppressed = suppression_repo::get_suppressed_fingerprints(pool, scan_id)
+        .await
+        .map_err(|e| format!("failed to load suppressions: {}", e))?;
+
+    let planned: Vec<_> = scanners
+        .scanners()
+        .iter()
+        .map(|scanner| (scanner.clone(), target.restricted_to(scanner.supported_kinds())))
+        .filter(|(_, source_target)| !source_target.is_empty())
+        .collect();
+    let source_names: Vec<String> = planned.iter().map(|(scanner, _)| scanner.name().to_string()).collect();
+    scan_repo::create_scan_sources(pool, scan_id, &source_names)
+        .await
+        .map_err(|e| format!("failed to record scan sources: {}", e))?;
+
+    let mut tasks = JoinSet::new();
+    for (scanner, source_target) in planned {
+        set_source_state(pool, scan_id, scanner.name(), SourceState::Started, 0, None).await;
+        let cancel = cancel.clone();
+        tasks.spawn(async move {
+            let findings = tokio::select! {
+                _ = cancel.cancelled() => None,
+                findings = scanner.scan(&source_target, &cancel) => Some(findings),
+            };
+            (scanner.name().to_string(), findings)
+        });
+    }
+
+    let mut sources_run = 0;
+    let mut sources_failed = 0;
+    while let Some(joined) = tasks.join_next().await {
+        let (source, findings) = match joined {
+            Ok((source, Some(Ok(findings)))) => (source, findings),
+            // Left for cancel_unfinished_sources below
+            Ok((_, None)) => continue,
+            Ok((source, Some(Err(e)))) => {
+                tracing::error!("Source {} failed for scan_id {}: {}", source, scan_id, e);
+                set_source_state(pool, scan_id, &source, SourceState::Failed, 0, Some(e.to_string())).await;
+                sources_run += 1;
+                sources_failed += 1;
+                continue;
+            }
+            Err(e) => {
+                tracing::error!("Source task panicked for scan_id {}: {}", scan_id, e);
+                sources_run += 1;
+                sources_failed += 1;
+                continue;
+            }
+        };
+        sources_run += 1;
+
+        // Whatever a source returns after cancellation is discarded
+        if cancel.is_cancelled() {
+            continue;
+        }
+
+        // Results the user reported as false positives are dropped
+        let findings: Vec<_> = findings
+            .into_iter()
+            .filter(|finding| !suppressed.contains(&fingerprint::fingerprint(&source, &finding.details)))
+            .collect();
+        let found = findings.len();
+        for mut finding in findings {
+            let (risk_level, matched_rules) = risk_rules.evaluate(&source, &finding);
+            finding.risk_level = risk_level;
+            let result = scan_repo::create_scan_result(pool, scan_id, &source, &finding, &matched_rules)
+                .await
+                .map_err(|e| format!("failed to store result from {}: {}", source, e))?;
+            // The finding stands without its evidence, so failing to keep it is only logged
+            if let (Some(capture), Some(finding_id)) = (&finding.capture, result.finding_id) {
+                if let Err(e) = evidence::record_capture(pool, blob_store, finding_id, Some(scan_id), capture).await {
+                    tracing::error!("Failed to keep evidence for result {}: {}", result.id, e);
+                }
+            }
+        }
+        set_source_state(pool, scan_id, &source, SourceState::Completed, found, None).await;
+    }
+
+    if cancel.is_cancelled() {
+        // Stopped without the scan being cancelled: the job's lease was lost and
+        // another worker owns the scan's sources now
+        let scan_cancelled = scan_repo::get_scan_by_id(pool, scan_id)
+            .await
+            .map_err(|e| format!("failed to load scan: {}", e))?
+            .is_some_and(|scan| scan.status == ScanStatus::Cancelled);
+        if !scan_cancelled {
+            tracing::warn!("Stopped scan_id {} without it being cancelled", scan_id);
+            return Ok(ScanOutcome::Cancelled);
+        }
+        let unfinished = scan_repo::cancel_unfinished_sources(pool, scan_id)
+            .await
+            .map_err(|e| format!("failed to cancel sources: {}", e))?;
+        for source in &unfinished {
+            publish_source_event(pool, scan_id, source, SourceState::Cancelled, None, None).await;
+        }
+        tracing::info!("Cancelled scan_id {}, {} sources did not finish", scan_id, unfinished.len());
+        return Ok(ScanOutcome::Cancelled);
+    }
+
+    if sources_run > 0 && sources_failed == sources_run {
+        return Err(format!("all {} sources failed", sources_run));
+    }
+
+    let score = score_scan(pool, scan_id).await;
+    // Stopped while scoring, e.g. the job's lease was lost and another worker is
+    // running the scan again: that run scores it
+    if cancel.is_cancelled() {
+        return Ok(ScanOutcome::Cancelled);
+    }
+
+    // Scored in the transaction that completes the scan, so the score is there once
+    // clients see the scan finish, and a scan cancelled meanwhile gets none
+    let mut tx = pool.begin().await.map_err(|e| format!("failed to complete scan: {}", e))?;
+    match scan_repo::update_scan_status_in(&mut tx, scan_id, ScanStatus::Completed).await {
+        Ok(_) => {}
+        // Cancelled after the last source finished
+        Err(StatusUpdateError::IllegalTransition { from: ScanStatus::Cancelled, .. }) => {
+            return Ok(ScanOutcome::Cancelled);
+        }
+        Err(e) => return Err(format!("failed to complete scan: {}", e)),
+    }
+    if let Some(score) = score {
+        store_score(&mut tx, scan_id, &score).await;
+    }
+    tx.commit().await.map_err(|e| format!("failed to complete scan: {}", e))?;
+
+    tracing::info!("Finished scan for scan_id: {}", scan_id);
+    Ok(ScanOutcome::Completed)
+}
+
+/// Computes the scan's privacy score. Failures are logged only: a missing score
+/// never fails the scan.
+async fn score_scan(pool: &PgPool, scan_id: Uuid) -> Option<PrivacyScore> {
+    match scan_repo::get_scan_results_by_scan(pool, scan_id).await {
+        Ok(results) => Some(score::score_results(&results)),
+        Err(e) => {
+            tracing::warn!("Failed to load results to score scan_id {}: {}", scan_id, e);
+            None
+        }
+    }
+}
+
+/// Stores the scan's privacy score under a savepoint, so failing to leaves the rest
+/// of `tx` intact. Failures are logged only, like in `score_scan`.
+async fn store_score(tx: &mut Transaction<'_, Postgres>, scan_id: Uuid, score: &PrivacyScore) {
+    let stored = async {
+        let mut savepoint = tx.begin().await?;
+        score_repo::save_score(&mut savepoint, scan_id, score).await?;
+        savepoint.commit().await
+    };
+    if let Err(e) = stored.await {
+        tracing::warn!("Failed to store score for scan_id {}: {}", scan_id, e);
+    }
+}
+
+/// Records a source's progress and publishes it. Failures are logged only: losing
+/// progress information never fails the scan.
+async fn set_source_state(
+    pool: &PgPool,
+    scan_id: Uuid,
+    source: &str,
+    state: SourceState,
+    findings: usize,
+    error: Option<String>,
+) {
+    let status = match state {
+        SourceState::Started => "running",
+        SourceState::Completed => "completed",
+        SourceState::Failed => "failed",
+        SourceState::Cancelled => "cancelled",
+    };
+    if let Err(e) =
+        scan_repo::update_scan_source(pool, scan_id, source, status, findings as i32, error.as_deref()).await
+    {
+        tracing::warn!("Failed to record progress of {} for scan_id {}: {}", source, scan_id, e);
+    }
+
+    let findings = (state == SourceState::Completed).then_some(findings);
+    publish_source_event(pool, scan_id, source, state, findings, error).await;
+}
+
+async fn publish_source_event(
+    pool: &PgPool,
+    scan_id: Uuid,
+    source: &str,
+    state: SourceState,
+    findings: Option<usize>,
+    error: Option<String>,
+) {
+    let notification = ScanNotification::Source {
+        scan_id,
+        source: source.to_string(),
+        state,
+        findings,
+        error,
+    };
+    if let Err(e) = events::publish(pool, &notification).await {
+        tracing::warn!("Failed to publish progress for scan_id {}: {}", scan_id, e);
+    }
+}
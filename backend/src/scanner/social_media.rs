@@ -0,0 +1,46 @@
+// src/scanner/social_media.rs
+
+use crate::{
+    models::scan::{FindingType, RiskLevel},
+    scanner::{Finding, IdentifierKind, ScanTarget, Scanner, ScannerError},
+};
+use async_trait::async_trait;
+use serde_json::json;
+use std::collections::HashSet;
+use tokio_util::sync::CancellationToken;
+
+/// Simulated social media lookup. Reports a profile for every username, and for
+/// the local part of every email.
+pub struct SocialMediaScanner;
+
+#[async_trait]
+impl Scanner for SocialMediaScanner {
+    fn name(&self) -> &str {
+        "social_media"
+    }
+
+    fn supported_kinds(&self) -> &[IdentifierKind] {
+        &[IdentifierKind::Email, IdentifierKind::Username]
+    }
+
+    async fn scan(
+        &self,
+        target: &ScanTarget,
+        _cancel: &CancellationToken,
+    ) -> Result<Vec<Finding>, ScannerError> {
+        let mut seen = HashSet::new();
+        let findings = target
+            .usernames()
+            .chain(target.emails().map(|email| email.split('@').next().unwrap_or("")))
+            .filter(|username| seen.insert(*username))
+            .map(|username| Finding {
+                finding_type: FindingType::SocialMedia,
+                details: json!({ "platform": "Twitter", "username": username }),
+                risk_level: RiskLevel::Low,
+                source_link: None,
+                capture: None,
+            })
+            .collect();
+        Ok(findings)
+    }
+}
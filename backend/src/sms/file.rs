@@ -0,0 +1,38 @@
+// src/sms/file.rs
+
+use crate::sms::{SmsError, SmsSender};
+use async_trait::async_trait;
+use serde_json::json;
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
+
+/// Development SMS sender that appends each message to a file, one JSON object per line.
+pub struct FileSmsSender {
+    path: PathBuf,
+}
+
+impl FileSmsSender {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl SmsSender for FileSmsSender {
+    async fn send(&self, to: &str, body: &str) -> Result<(), SmsError> {
+        let line = json!({ "to": to, "body": body, "sent_at": chrono::Utc::now() }).to_string();
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| SmsError(format!("failed to open {}: {}", self.path.display(), e)))?;
+        file.write_all(format!("{}\n", line).as_bytes())
+            .await
+            .map_err(|e| SmsError(format!("failed to write {}: {}", self.path.display(), e)))?;
+        // Dropping a tokio file doesn't wait for the write to land
+        file.flush()
+            .await
+            .map_err(|e| SmsError(format!("failed to write {}: {}", self.path.display(), e)))
+    }
+}
@@ -0,0 +1,31 @@
+// src/sms/mod.rs
+
+pub mod file;
+
+use async_trait::async_trait;
+use std::{env, fmt, sync::Arc};
+
+#[derive(Debug)]
+pub struct SmsError(pub String);
+
+impl fmt::Display for SmsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for SmsError {}
+
+/// Outbound text message delivery.
+#[async_trait]
+pub trait SmsSender: Send + Sync {
+    /// Sends `body` to `to`, an E.164 phone number.
+    async fn send(&self, to: &str, body: &str) -> Result<(), SmsError>;
+}
+
+/// SMS sender configured by `SMS_OUTBOX_FILE` (default `sms_outbox.log`). Messages
+/// are appended to that file instead of being delivered.
+pub fn sms_sender_from_env() -> Arc<dyn SmsSender> {
+    let path = env::var("SMS_OUTBOX_FILE").unwrap_or_else(|_| "sms_outbox.log".into());
+    Arc::new(file::FileSmsSender::new(path))
+}
@@ -0,0 +1,80 @@
+// src/startup.rs
+
+// Process setup shared by the API server and the scan worker binaries.
+
+use crate::{
+    brokers::{BrokerCatalog, DEFAULT_CATALOG_DIR},
+    mail::templates::{self, MailTemplates},
+    matching::DEFAULT_CONFIDENCE_THRESHOLD,
+    risk::{RiskRules, DEFAULT_RULES_FILE},
+    scanner::ScannerRegistry,
+    takedown::{TakedownTemplates, DEFAULT_TEMPLATE_DIR},
+};
+use sqlx::{postgres::PgPoolOptions, PgPool};
+use std::{env, time::Duration};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+pub fn init_tracing() {
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::new(
+            env::var("RUST_LOG").unwrap_or_else(|_| "shadow_scan_backend=debug,shadow_scan_worker=debug,tower_http=debug".into()),
+        ))
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+}
+
+pub async fn connect_db(max_connections: u32) -> PgPool {
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    PgPoolOptions::new()
+        .max_connections(max_connections)
+        .connect(&database_url)
+        .await
+        .expect("Failed to create pool.")
+}
+
+/// Built-in scanners plus one per broker in the catalog at `BROKER_CATALOG_DIR`.
+/// Broker records are kept when they match the user with at least
+/// `MATCH_CONFIDENCE_THRESHOLD` confidence (0 to 1).
+pub fn scanner_registry() -> ScannerRegistry {
+    let catalog_dir = env::var("BROKER_CATALOG_DIR").unwrap_or_else(|_| DEFAULT_CATALOG_DIR.into());
+    let catalog = BrokerCatalog::load_dir(&catalog_dir).expect("Failed to load broker catalog");
+    tracing::debug!("loaded {} brokers from {}", catalog.brokers.len(), catalog_dir);
+
+    let http_client = reqwest::Client::builder()
+        .user_agent(concat!("ShadowScan/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(30))
+        .build()
+        .expect("Failed to create HTTP client.");
+
+    let mut scanners = ScannerRegistry::with_defaults();
+    let match_threshold = env::var("MATCH_CONFIDENCE_THRESHOLD")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_CONFIDENCE_THRESHOLD);
+    scanners.register_brokers(&catalog, &http_client, match_threshold);
+    scanners
+}
+
+/// Risk rules from the file at `RISK_RULES_FILE`.
+pub fn risk_rules() -> RiskRules {
+    let rules_file = env::var("RISK_RULES_FILE").unwrap_or_else(|_| DEFAULT_RULES_FILE.into());
+    let rules = RiskRules::load_file(&rules_file).expect("Failed to load risk rules");
+    tracing::debug!("loaded {} risk rules from {}", rules.rules.len(), rules_file);
+    rules
+}
+
+/// Takedown letter templates from the directory at `TAKEDOWN_TEMPLATE_DIR`.
+pub fn takedown_templates() -> TakedownTemplates {
+    let template_dir = env::var("TAKEDOWN_TEMPLATE_DIR").unwrap_or_else(|_| DEFAULT_TEMPLATE_DIR.into());
+    let templates = TakedownTemplates::load_dir(&template_dir).expect("Failed to load takedown templates");
+    tracing::debug!("loaded {} takedown templates from {}", templates.template_count(), template_dir);
+    templates
+}
+
+/// Email templates from the directory at `MAIL_TEMPLATE_DIR`.
+pub fn mail_templates() -> MailTemplates {
+    let template_dir = env::var("MAIL_TEMPLATE_DIR").unwrap_or_else(|_| templates::DEFAULT_TEMPLATE_DIR.into());
+    let templates = MailTemplates::load_dir(&template_dir).expect("Failed to load mail templates");
+    tracing::debug!("loaded {} mail templates from {}", templates.template_count(), template_dir);
+    templates
+}
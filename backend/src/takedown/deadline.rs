@@ -0,0 +1,68 @@
+// src/takedown/deadline.rs
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Member states of the EEA, plus the UK, whose data protection law mirrors the GDPR.
+const GDPR_COUNTRIES: &[&str] = &[
+    "at", "be", "bg", "hr", "cy", "cz", "dk", "ee", "fi", "fr", "de", "gr", "hu", "ie", "it", "lv", "lt",
+    "lu", "mt", "nl", "pl", "pt", "ro", "sk", "si", "es", "se", "is", "li", "no", "uk", "gb",
+];
+
+/// The law a takedown request is made under, which sets how long the broker has
+/// to respond.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Regime {
+    /// GDPR Art. 12(3): one month, extendable by two more.
+    Gdpr,
+    /// CCPA § 1798.130: 45 days, extendable once by another 45.
+    Ccpa,
+    /// No statutory right; brokers are given the GDPR's month before following up.
+    Voluntary,
+}
+
+impl Regime {
+    /// The regime for a jurisdiction such as `eu`, `de` or `us-ca`.
+    pub fn for_jurisdiction(jurisdiction: &str) -> Self {
+        let jurisdiction = jurisdiction.trim().to_lowercase();
+        let country = jurisdiction.split('-').next().unwrap_or_default();
+        if matches!(country, "eu" | "eea") || GDPR_COUNTRIES.contains(&country) {
+            Regime::Gdpr
+        } else if jurisdiction == "us-ca" {
+            Regime::Ccpa
+        } else {
+            Regime::Voluntary
+        }
+    }
+
+    pub fn response_days(self) -> i64 {
+        match self {
+            Regime::Gdpr => 30,
+            Regime::Ccpa => 45,
+            Regime::Voluntary => 30,
+        }
+    }
+
+    /// The longest extension a broker may take on top of `response_days`.
+    pub fn max_extension_days(self) -> i64 {
+        match self {
+            Regime::Gdpr => 60,
+            Regime::Ccpa => 45,
+            Regime::Voluntary => 0,
+        }
+    }
+
+    /// Template set holding the letters for this regime.
+    pub fn template_set(self) -> Option<&'static str> {
+        match self {
+            Regime::Gdpr => Some("eu"),
+            Regime::Ccpa => Some("us-ca"),
+            Regime::Voluntary => None,
+        }
+    }
+}
+
+/// When a request sent at `sent_at` must be answered by, with `extension_days`
+/// already granted.
+pub fn deadline(jurisdiction: &str, sent_at: DateTime<Utc>, extension_days: i64) -> DateTime<Utc> {
+    sent_at + Duration::days(Regime::for_jurisdiction(jurisdiction).response_days() + extension_days)
+}
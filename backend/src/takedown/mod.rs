@@ -0,0 +1,343 @@
+// src/takedown/mod.rs
+
+// Takedown letters: erasure and opt-out requests sent to data brokers. Letters are
+// Tera templates in the template directory, one set per jurisdiction (e.g. `eu/`,
+// `us-ca/`) with `default/` as the fallback, and optional per-broker sets under
+// `brokers/<broker id>/` that take precedence. Each set holds a subject, text and
+// HTML template per letter kind, e.g. `request.subject.txt`, `request.txt` and
+// `request.html`.
+
+pub mod deadline;
+
+use crate::{
+    mail::EmailMessage,
+    models::{
+        evidence::EvidenceSnapshot,
+        finding::TrackedFinding,
+        profile::ProfileIdentifier,
+        takedown::{TakedownChannel, TakedownRequest, TakedownResponse, TakedownResponseKind},
+        user::User,
+    },
+    scanner::Identifier,
+};
+use chrono::{DateTime, Utc};
+use deadline::Regime;
+use serde::Serialize;
+use std::{fmt, path::Path};
+use tera::{Context, Tera};
+
+pub const DEFAULT_TEMPLATE_DIR: &str = "config/takedown";
+
+const FALLBACK_SET: &str = "default";
+
+/// Most evidence snapshots cited in an escalation, besides the first capture.
+const MAX_ESCALATION_EVIDENCE: usize = 10;
+
+#[derive(Debug)]
+pub enum TemplateError {
+    Load(tera::Error),
+    Missing(String),
+    Render(String, tera::Error),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::Load(e) => write!(f, "failed to load takedown templates: {}", e),
+            TemplateError::Missing(set) => write!(f, "no takedown template set for {}", set),
+            // Tera keeps the useful part of the message in the error's source
+            TemplateError::Render(name, e) => match std::error::Error::source(e) {
+                Some(cause) => write!(f, "failed to render {}: {}", name, cause),
+                None => write!(f, "failed to render {}: {}", name, e),
+            },
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// The person asking for their data to be removed.
+#[derive(Debug, Clone, Serialize)]
+pub struct Requester {
+    pub full_name: String,
+    /// Where the broker should reply.
+    pub email: String,
+}
+
+impl Requester {
+    /// The user's name as their profile gives it, or their username without one.
+    pub fn new(user: &User, identifiers: &[ProfileIdentifier]) -> Self {
+        let full_name = identifiers
+            .iter()
+            .filter(|profile_identifier| !profile_identifier.alias)
+            .find_map(|profile_identifier| match &profile_identifier.identifier {
+                Identifier::Name { first_name, middle_name, last_name, .. } => Some(
+                    [Some(first_name), middle_name.as_ref(), Some(last_name)]
+                        .into_iter()
+                        .flatten()
+                        .map(String::as_str)
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ),
+                _ => None,
+            })
+            .unwrap_or_else(|| user.username.clone());
+        Self { full_name, email: user.email.clone() }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BrokerInfo {
+    pub id: String,
+    pub name: String,
+    pub jurisdiction: String,
+    pub contact_email: Option<String>,
+    pub opt_out_url: Option<String>,
+}
+
+impl BrokerInfo {
+    /// Broker details recorded with the listing when it was found.
+    pub fn from_finding(finding: &TrackedFinding) -> Self {
+        let detail = |key: &str| finding.details.get(key).and_then(|value| value.as_str()).map(str::to_string);
+        Self {
+            id: detail("broker").unwrap_or_else(|| finding.source.clone()),
+            name: detail("broker_name").unwrap_or_else(|| finding.source.clone()),
+            jurisdiction: detail("jurisdiction").unwrap_or_default(),
+            contact_email: detail("contact_email"),
+            opt_out_url: detail("opt_out_url"),
+        }
+    }
+}
+
+/// The listing to remove, as the scan found it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Listing {
+    pub url: Option<String>,
+    /// Fields extracted from the broker's page, e.g. name, age and address.
+    pub record: serde_json::Value,
+    pub first_seen: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TakedownContext {
+    pub requester: Requester,
+    pub broker: BrokerInfo,
+    pub listing: Listing,
+    /// Jurisdiction whose law the letter invokes.
+    pub jurisdiction: String,
+    pub date: String,
+}
+
+impl TakedownContext {
+    /// Context for a letter about `finding`, invoking the law of `jurisdiction`
+    /// or, without one, of the broker's jurisdiction.
+    pub fn new(requester: Requester, finding: &TrackedFinding, jurisdiction: Option<&str>) -> Self {
+        let broker = BrokerInfo::from_finding(finding);
+        let jurisdiction = jurisdiction
+            .map(|jurisdiction| jurisdiction.trim().to_lowercase())
+            .filter(|jurisdiction| !jurisdiction.is_empty())
+            .unwrap_or_else(|| broker.jurisdiction.clone());
+        Self {
+            requester,
+            broker,
+            listing: Listing {
+                url: finding.source_link.clone(),
+                record: finding.details.get("record").cloned().unwrap_or_default(),
+                first_seen: format_date(finding.first_seen_at),
+            },
+            jurisdiction,
+            date: format_date(Utc::now()),
+        }
+    }
+}
+
+/// The request a broker failed to answer, for escalation letters.
+#[derive(Debug, Clone, Serialize)]
+pub struct SentRequest {
+    pub channel: TakedownChannel,
+    pub subject: String,
+    pub sent: String,
+    pub deadline: String,
+    pub responses: Vec<ReceivedResponse>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReceivedResponse {
+    pub kind: TakedownResponseKind,
+    pub received: String,
+    pub note: Option<String>,
+}
+
+impl SentRequest {
+    pub fn new(request: &TakedownRequest, responses: &[TakedownResponse]) -> Self {
+        Self {
+            channel: request.channel,
+            subject: request.subject.clone(),
+            sent: format_date(request.sent_at),
+            deadline: format_date(request.deadline_at),
+            responses: responses
+                .iter()
+                .map(|response| ReceivedResponse {
+                    kind: response.kind,
+                    received: format_date(response.received_at),
+                    note: response.note.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// An evidence snapshot as cited in a letter.
+#[derive(Debug, Clone, Serialize)]
+pub struct CapturedEvidence {
+    pub captured: String,
+    pub url: String,
+    pub http_status: i16,
+    pub body_hash: String,
+    pub chain_hash: String,
+}
+
+impl CapturedEvidence {
+    pub fn new(snapshot: &EvidenceSnapshot) -> Self {
+        Self {
+            captured: snapshot.captured_at.format("%-d %B %Y %H:%M UTC").to_string(),
+            url: snapshot.url.clone(),
+            http_status: snapshot.http_status,
+            body_hash: snapshot.body_hash.clone(),
+            chain_hash: snapshot.chain_hash.clone(),
+        }
+    }
+}
+
+/// The snapshots an escalation cites, in chain order: the finding's first capture
+/// and the latest ones taken since the request was sent, which show the broker
+/// kept the listing up.
+pub fn escalation_evidence(snapshots: &[EvidenceSnapshot], sent_at: DateTime<Utc>) -> Vec<&EvidenceSnapshot> {
+    let since_sent: Vec<_> = snapshots.iter().filter(|snapshot| snapshot.captured_at >= sent_at).collect();
+    let latest = &since_sent[since_sent.len().saturating_sub(MAX_ESCALATION_EVIDENCE)..];
+    snapshots
+        .first()
+        .filter(|first| !latest.iter().any(|snapshot| snapshot.id == first.id))
+        .into_iter()
+        .chain(latest.iter().copied())
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EscalationContext {
+    #[serde(flatten)]
+    pub letter: TakedownContext,
+    pub request: SentRequest,
+    /// Captures of the listing, oldest first.
+    pub evidence: Vec<CapturedEvidence>,
+}
+
+fn format_date(date: DateTime<Utc>) -> String {
+    date.format("%-d %B %Y").to_string()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TakedownLetter {
+    /// Template set the letter was rendered from, e.g. `eu` or `brokers/spokeo`.
+    pub template_set: String,
+    pub jurisdiction: String,
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+    /// Ready to send when the broker has a contact address; otherwise use the
+    /// broker's opt-out form.
+    pub email: Option<EmailMessage>,
+}
+
+pub struct TakedownTemplates {
+    tera: Tera,
+}
+
+impl TakedownTemplates {
+    /// Loads every template under `dir`. A missing directory yields no templates,
+    /// so rendering fails until some are installed.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Self, TemplateError> {
+        let dir = dir.as_ref();
+        if !dir.exists() {
+            return Ok(Self { tera: Tera::default() });
+        }
+        let pattern = format!("{}/**/*", dir.display());
+        let tera = Tera::new(&pattern).map_err(TemplateError::Load)?;
+        Ok(Self { tera })
+    }
+
+    pub fn template_count(&self) -> usize {
+        self.tera.get_template_names().count()
+    }
+
+    /// The most specific template set with a `kind` letter for the broker and
+    /// jurisdiction: the broker's own set, then the jurisdiction (`us-ca`), its
+    /// country (`us`), the set for its law (`eu` for `de`) and the default.
+    pub fn template_set(&self, kind: &str, broker_id: &str, jurisdiction: &str) -> Option<String> {
+        let jurisdiction = jurisdiction.trim().to_lowercase();
+        let mut candidates = vec![format!("brokers/{}", broker_id), jurisdiction.clone()];
+        if let Some((country, _)) = jurisdiction.split_once('-') {
+            candidates.push(country.to_string());
+        }
+        if let Some(set) = Regime::for_jurisdiction(&jurisdiction).template_set() {
+            candidates.push(set.to_string());
+        }
+        candidates.push(FALLBACK_SET.to_string());
+
+        candidates
+            .into_iter()
+            .find(|set| self.tera.get_template(&format!("{}/{}.txt", set, kind)).is_ok())
+    }
+
+    /// The erasure or opt-out request to send to the broker.
+    pub fn render_request(&self, context: &TakedownContext) -> Result<TakedownLetter, TemplateError> {
+        let mut letter = self.render_letter("request", context, context)?;
+        letter.email = context.broker.contact_email.as_ref().map(|to| EmailMessage {
+            to: to.clone(),
+            subject: letter.subject.clone(),
+            text_body: letter.text.clone(),
+            html_body: Some(letter.html.clone()),
+            reply_to: Some(context.requester.email.clone()),
+        });
+        Ok(letter)
+    }
+
+    /// The complaint to the supervisory authority about a broker that did not
+    /// answer a request in time.
+    pub fn render_escalation(&self, context: &EscalationContext) -> Result<TakedownLetter, TemplateError> {
+        self.render_letter("escalation", &context.letter, context)
+    }
+
+    fn render_letter(
+        &self,
+        kind: &str,
+        letter: &TakedownContext,
+        context: &impl Serialize,
+    ) -> Result<TakedownLetter, TemplateError> {
+        let set = self
+            .template_set(kind, &letter.broker.id, &letter.jurisdiction)
+            .ok_or_else(|| TemplateError::Missing(format!("{} ({})", letter.jurisdiction, kind)))?;
+        let context = Context::from_serialize(context).map_err(TemplateError::Load)?;
+
+        let subject = self.render(&set, &format!("{}.subject.txt", kind), &context)?;
+        Ok(TakedownLetter {
+            subject: subject.trim().to_string(),
+            text: self.render(&set, &format!("{}.txt", kind), &context)?,
+            html: self.render(&set, &format!("{}.html", kind), &context)?,
+            template_set: set,
+            jurisdiction: letter.jurisdiction.clone(),
+            email: None,
+        })
+    }
+
+    /// Renders `file` from `set`, falling back to the default set so a broker or
+    /// jurisdiction can override only some of the files.
+    fn render(&self, set: &str, file: &str, context: &Context) -> Result<String, TemplateError> {
+        let name = [set, FALLBACK_SET]
+            .iter()
+            .map(|set| format!("{}/{}", set, file))
+            .find(|name| self.tera.get_template(name).is_ok())
+            .ok_or_else(|| TemplateError::Missing(format!("{}/{}", set, file)))?;
+        self.tera.render(&name, context).map_err(|e| TemplateError::Render(name, e))
+    }
+}
@@ -0,0 +1,118 @@
+// tests/broker_extraction.rs
+
+// Runs the catalog's extraction rules against saved broker result pages, and
+// checks how search URLs are built from identifiers.
+
+use serde_json::{json, Map, Value};
+use shadow_scan_backend::{
+    brokers::{
+        extract::{extract_records, shows_no_results},
+        Broker, BrokerSearch,
+    },
+    scanner::{broker::build_search_url, Identifier, IdentifierKind},
+};
+use std::{collections::HashMap, fs};
+
+fn broker(id: &str) -> Broker {
+    let contents = fs::read_to_string(format!("config/brokers/{}.toml", id)).unwrap();
+    Broker::from_toml(&contents).unwrap()
+}
+
+fn fixture(name: &str) -> String {
+    fs::read_to_string(format!("tests/fixtures/brokers/{}.html", name)).unwrap()
+}
+
+fn record(value: Value) -> Map<String, Value> {
+    value.as_object().unwrap().clone()
+}
+
+#[test]
+fn extracts_fastpeoplesearch_cards() {
+    let records = extract_records(&broker("fastpeoplesearch").extract, &fixture("fastpeoplesearch")).unwrap();
+
+    // The sponsored card has none of the fields and is skipped
+    assert_eq!(
+        records,
+        vec![
+            record(json!({
+                "name": "Alice J Smith",
+                "age": "Age 34",
+                "home_address": "12 Oak St Portland OR 97201",
+                "phone": ["(555) 123-4567", "(555) 987-6543"],
+                "profile_url": "/alice-smith_id_G123",
+            })),
+            record(json!({
+                "name": "Alice Smith",
+                "profile_url": "/alice-smith_id_G456",
+            })),
+        ]
+    );
+}
+
+#[test]
+fn extracts_thatsthem_records() {
+    let records = extract_records(&broker("thatsthem").extract, &fixture("thatsthem")).unwrap();
+
+    assert_eq!(
+        records,
+        vec![record(json!({
+            "name": "Alice Smith",
+            "home_address": "12 Oak St, Portland, OR 97201",
+            "phone": ["(555) 123-4567"],
+            "email": ["alice@example.com", "asmith@example.org"],
+            "profile_url": "https://thatsthem.com/name/Alice-Smith/Portland-OR",
+        }))]
+    );
+}
+
+#[test]
+fn page_without_records_extracts_nothing() {
+    assert!(extract_records(&broker("thatsthem").extract, &fixture("empty")).unwrap().is_empty());
+}
+
+#[test]
+fn no_results_page_is_recognized_by_its_text() {
+    let thatsthem = broker("thatsthem");
+    assert!(shows_no_results(&thatsthem.extract, &fixture("empty")));
+    assert!(!shows_no_results(&thatsthem.extract, &fixture("thatsthem")));
+
+    // Brokers without the text only say so with a 404
+    assert!(!shows_no_results(&broker("fastpeoplesearch").extract, &fixture("empty")));
+}
+
+#[test]
+fn search_url_encodes_path_values() {
+    let search = broker("fastpeoplesearch").search_for(IdentifierKind::Address).unwrap().clone();
+    let address = Identifier::Address {
+        street: "12 Oak St #4".to_string(),
+        city: "Portland".to_string(),
+        state: "OR".to_string(),
+        postal_code: None,
+    };
+
+    let url = build_search_url(&search, &address).unwrap();
+
+    assert_eq!(url.as_str(), "https://www.fastpeoplesearch.com/address/12%20Oak%20St%20%234_Portland-OR");
+}
+
+#[test]
+fn substituted_values_are_not_substituted_again() {
+    let search = BrokerSearch {
+        kind: IdentifierKind::Name,
+        url_template: "https://example.com/{first_name}/{last_name}".to_string(),
+        query: HashMap::from([("q".to_string(), "{first_name} {last_name} {unknown}".to_string())]),
+    };
+    let name = Identifier::Name {
+        first_name: "{last_name}".to_string(),
+        middle_name: None,
+        last_name: "Smith".to_string(),
+        city: None,
+        state: None,
+    };
+
+    let url = build_search_url(&search, &name).unwrap();
+
+    assert_eq!(url.path(), "/%7Blast_name%7D/Smith");
+    let query: Vec<_> = url.query_pairs().collect();
+    assert_eq!(query[0].1, "{last_name} Smith {unknown}");
+}
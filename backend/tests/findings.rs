@@ -0,0 +1,249 @@
+// tests/findings.rs
+
+// Finding fingerprints, how scans are compared with them, and privacy scores.
+
+use chrono::Utc;
+use serde_json::{json, Value};
+use shadow_scan_backend::{
+    findings::{
+        diff::ScanDiff,
+        fingerprint::fingerprint,
+        score::{categories, score_results, DataCategory, FactorKind, PrivacyScore},
+    },
+    models::scan::{FindingType, RiskLevel, ScanResult},
+};
+use uuid::Uuid;
+
+fn broker_details(record: Value, listing_url: Option<&str>) -> Value {
+    json!({
+        "broker": "fastpeoplesearch",
+        "broker_name": "FastPeopleSearch",
+        "searched": { "kind": "email", "value": "alice@example.com" },
+        "record": record,
+        "listing_url": listing_url,
+        "match": { "confidence": 0.95 },
+        "opt_out_url": "https://www.fastpeoplesearch.com/removal",
+        "jurisdiction": "us-ca",
+        "contact_email": "info@fastpeoplesearch.com",
+    })
+}
+
+fn result(scan_id: Uuid, details: Value) -> ScanResult {
+    ScanResult {
+        id: Uuid::new_v4(),
+        scan_id,
+        source: "breach_db".to_string(),
+        fingerprint: fingerprint("breach_db", &details),
+        finding_id: None,
+        finding_type: FindingType::EmailLeak,
+        details,
+        risk_level: RiskLevel::High,
+        risk_rules: Vec::new(),
+        source_link: None,
+        found_at: Utc::now(),
+    }
+}
+
+#[test]
+fn fingerprint_ignores_key_order() {
+    let a: Value = serde_json::from_str(r#"{"platform": "Twitter", "username": "alice"}"#).unwrap();
+    let b: Value = serde_json::from_str(r#"{"username": "alice", "platform": "Twitter"}"#).unwrap();
+    assert_eq!(fingerprint("social_media", &a), fingerprint("social_media", &b));
+}
+
+#[test]
+fn fingerprint_ignores_case_and_whitespace() {
+    let a = json!({ "leaked_email": "alice@example.com", "tags": ["a  b"] });
+    let b = json!({ "leaked_email": "  Alice@Example.com ", "tags": [" A b"] });
+    assert_eq!(fingerprint("breach_db", &a), fingerprint("breach_db", &b));
+}
+
+#[test]
+fn fingerprint_ignores_source_description_and_nulls() {
+    let a = json!({ "leaked_email": "alice@example.com", "source": "Simulated Breach DB" });
+    let b = json!({ "leaked_email": "alice@example.com", "source": "Renamed DB", "note": null });
+    assert_eq!(fingerprint("breach_db", &a), fingerprint("breach_db", &b));
+
+    // A key is only ignored at the top level
+    let c = json!({ "leaked_email": "alice@example.com", "extra": { "source": "x" } });
+    let d = json!({ "leaked_email": "alice@example.com", "extra": { "source": "y" } });
+    assert_ne!(fingerprint("breach_db", &c), fingerprint("breach_db", &d));
+}
+
+#[test]
+fn fingerprint_depends_on_source_and_content() {
+    let details = json!({ "leaked_email": "alice@example.com" });
+    assert_ne!(fingerprint("breach_db", &details), fingerprint("other_db", &details));
+    assert_ne!(
+        fingerprint("breach_db", &details),
+        fingerprint("breach_db", &json!({ "leaked_email": "bob@example.com" }))
+    );
+}
+
+#[test]
+fn linked_listing_is_identified_by_its_url() {
+    let url = Some("https://www.fastpeoplesearch.com/alice-smith_id_G123");
+    let before = broker_details(json!({ "name": "Alice Smith", "age": "Age 34", "phone": ["(555) 123-4567"] }), url);
+    let after = broker_details(
+        json!({ "name": "Alice J Smith", "age": "Age 35", "phone": ["(555) 987-6543"], "profile_url": "/x" }),
+        url,
+    );
+    assert_eq!(fingerprint("fastpeoplesearch", &before), fingerprint("fastpeoplesearch", &after));
+
+    let other = broker_details(
+        json!({ "name": "Alice Smith" }),
+        Some("https://www.fastpeoplesearch.com/alice-smith_id_G456"),
+    );
+    assert_ne!(fingerprint("fastpeoplesearch", &before), fingerprint("fastpeoplesearch", &other));
+}
+
+#[test]
+fn unlinked_listing_is_identified_by_name_and_address() {
+    let before = broker_details(
+        json!({ "name": "Alice Smith", "home_address": "12 Oak St", "age": "Age 34" }),
+        None,
+    );
+    let birthday = broker_details(
+        json!({ "name": "Alice Smith", "home_address": "12 Oak St", "age": "Age 35", "phone": ["(555) 123-4567"] }),
+        None,
+    );
+    let moved = broker_details(json!({ "name": "Alice Smith", "home_address": "9 Elm St" }), None);
+
+    assert_eq!(fingerprint("fastpeoplesearch", &before), fingerprint("fastpeoplesearch", &birthday));
+    assert_ne!(fingerprint("fastpeoplesearch", &before), fingerprint("fastpeoplesearch", &moved));
+}
+
+#[test]
+fn listing_without_key_fields_ignores_age() {
+    let before = broker_details(json!({ "email": ["alice@example.com"], "age": "Age 34" }), None);
+    let after = broker_details(json!({ "email": ["alice@example.com"], "age": "Age 35" }), None);
+    assert_eq!(fingerprint("fastpeoplesearch", &before), fingerprint("fastpeoplesearch", &after));
+}
+
+#[test]
+fn diff_sorts_results_into_new_persisting_and_resolved() {
+    let (previous_id, current_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let kept = json!({ "leaked_email": "alice@example.com" });
+    let gone = json!({ "leaked_email": "old@example.com" });
+    let added = json!({ "leaked_email": "new@example.com" });
+
+    let previous = vec![result(previous_id, kept.clone()), result(previous_id, gone.clone())];
+    // Found twice in the current scan, counted once
+    let current = vec![result(current_id, kept.clone()), result(current_id, added.clone()), result(current_id, added)];
+
+    let diff = ScanDiff::between(current_id, &current, previous_id, &previous);
+
+    assert_eq!((diff.summary.new, diff.summary.persisting, diff.summary.resolved), (1, 1, 1));
+    assert_eq!(diff.new[0].details["leaked_email"], "new@example.com");
+    assert_eq!(diff.persisting[0].scan_id, current_id);
+    assert_eq!(diff.resolved[0].scan_id, previous_id);
+    assert_eq!(diff.resolved[0].details, gone);
+}
+
+#[test]
+fn diff_against_identical_scan_has_no_changes() {
+    let (previous_id, current_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let details = json!({ "leaked_email": "alice@example.com" });
+
+    let diff = ScanDiff::between(
+        current_id,
+        &[result(current_id, details.clone())],
+        previous_id,
+        &[result(previous_id, details)],
+    );
+
+    assert_eq!((diff.summary.new, diff.summary.persisting, diff.summary.resolved), (0, 1, 0));
+}
+
+fn scored(finding_type: FindingType, risk_level: RiskLevel, details: Value) -> ScanResult {
+    ScanResult {
+        source: "fastpeoplesearch".to_string(),
+        fingerprint: fingerprint("fastpeoplesearch", &details),
+        finding_type,
+        risk_level,
+        ..result(Uuid::nil(), details)
+    }
+}
+
+fn penalty(score: &PrivacyScore, kind: FactorKind, name: &str) -> Option<(usize, i32)> {
+    score
+        .breakdown
+        .iter()
+        .find(|factor| factor.kind == kind && factor.name == name)
+        .map(|factor| (factor.count, factor.penalty))
+}
+
+#[test]
+fn nothing_found_scores_full_marks() {
+    let score = score_results(&[]);
+    assert_eq!(score.score, 100);
+    assert!(score.breakdown.is_empty());
+}
+
+#[test]
+fn broker_records_are_categorized_by_exact_field_names() {
+    let record = scored(
+        FindingType::DataBroker,
+        RiskLevel::High,
+        json!({ "record": { "home_address": "12 Oak St", "phone": ["(555) 123-4567"], "age": "Age 34", "name": "Alice" } }),
+    );
+    let mut found = categories(&record);
+    found.sort();
+    assert_eq!(found, vec![DataCategory::HomeAddress, DataCategory::Phone, DataCategory::DateOfBirth]);
+
+    let lookalikes = scored(
+        FindingType::DataBroker,
+        RiskLevel::High,
+        json!({ "record": { "email_address": "alice@example.com", "ip_address": "10.0.0.1", "phone": [] } }),
+    );
+    assert!(categories(&lookalikes).is_empty());
+}
+
+#[test]
+fn results_sharing_a_fingerprint_count_once() {
+    let details = json!({ "leaked_email": "alice@example.com" });
+    let results = [
+        scored(FindingType::EmailLeak, RiskLevel::High, details.clone()),
+        scored(FindingType::EmailLeak, RiskLevel::High, details),
+    ];
+
+    let score = score_results(&results);
+
+    assert_eq!(penalty(&score, FactorKind::Risk, "high"), Some((1, 6)));
+    assert_eq!(penalty(&score, FactorKind::Category, "email"), Some((1, 4)));
+    assert_eq!(score.score, 90);
+}
+
+#[test]
+fn risk_penalties_are_capped_per_level() {
+    let results: Vec<_> = (0..25)
+        .map(|i| scored(FindingType::SocialMedia, RiskLevel::Low, json!({ "username": format!("alice{}", i) })))
+        .collect();
+
+    let score = score_results(&results);
+
+    // 25 low findings at 1 point each, capped at 10; the category counts once
+    assert_eq!(penalty(&score, FactorKind::Risk, "low"), Some((25, 10)));
+    assert_eq!(penalty(&score, FactorKind::Category, "social_profile"), Some((25, 3)));
+    assert_eq!(score.score, 87);
+}
+
+#[test]
+fn score_never_drops_below_zero() {
+    let mut results: Vec<_> = (0..10)
+        .map(|i| scored(FindingType::PasswordLeak, RiskLevel::Critical, json!({ "leak": i })))
+        .collect();
+    results.extend((0..10).map(|i| {
+        scored(
+            FindingType::DataBroker,
+            RiskLevel::High,
+            json!({ "record": { "name": format!("Alice {}", i), "home_address": "12 Oak St", "phone": ["1"], "age": "34", "relatives": ["Bob"] } }),
+        )
+    }));
+
+    let score = score_results(&results);
+
+    let total: i32 = score.breakdown.iter().map(|factor| factor.penalty).sum();
+    assert!(total > 100);
+    assert_eq!(score.score, 0);
+}
@@ -0,0 +1,182 @@
+// tests/matching.rs
+
+// Telling the user's broker records apart from namesakes'.
+
+use chrono::{Datelike, Utc};
+use serde_json::{json, Map, Value};
+use shadow_scan_backend::{
+    matching::{match_record, MatchProfile, RecordMatch},
+    scanner::Identifier,
+};
+
+fn name(first: &str, last: &str, city: Option<&str>, state: Option<&str>) -> Identifier {
+    Identifier::Name {
+        first_name: first.to_string(),
+        middle_name: None,
+        last_name: last.to_string(),
+        city: city.map(str::to_string),
+        state: state.map(str::to_string),
+    }
+}
+
+fn email(value: &str) -> Identifier {
+    Identifier::Email { value: value.to_string() }
+}
+
+fn phone(value: &str) -> Identifier {
+    Identifier::Phone { value: value.to_string() }
+}
+
+fn record(value: Value) -> Map<String, Value> {
+    value.as_object().unwrap().clone()
+}
+
+fn address(street: &str) -> Identifier {
+    Identifier::Address {
+        street: street.to_string(),
+        city: "Portland".to_string(),
+        state: "OR".to_string(),
+        postal_code: None,
+    }
+}
+
+fn profile(identifiers: Vec<Identifier>, birth_year: Option<i32>) -> MatchProfile {
+    MatchProfile { identifiers, birth_year }
+}
+
+fn signal(matched: &RecordMatch, name: &str) -> Option<f64> {
+    matched.signals.iter().find(|signal| signal.signal == name).map(|signal| signal.score)
+}
+
+#[test]
+fn nickname_counts_as_the_same_first_name() {
+    let alice = name("Robert", "Smith", None, None);
+    let matched = match_record(&record(json!({ "name": "Bob Smith" })), &profile(vec![alice.clone()], None), &alice);
+
+    assert_eq!(signal(&matched, "name"), Some(1.0));
+    assert!(matched.signals[0].explanation.contains("nickname"));
+    assert_eq!(matched.confidence, 1.0);
+}
+
+#[test]
+fn only_close_spellings_score_on_names() {
+    let alice = name("Alice", "Smith", None, None);
+    let profile = profile(vec![alice.clone()], None);
+
+    let typo = match_record(&record(json!({ "name": "Alice Smyth" })), &profile, &alice);
+    let typo_score = signal(&typo, "name").unwrap();
+    assert!(typo_score > 0.6 && typo_score < 1.0, "{}", typo_score);
+
+    // Unrelated last names score below 0.75 on Jaro-Winkler, which rescales to nothing
+    let stranger = match_record(&record(json!({ "name": "Alice Jones" })), &profile, &alice);
+    assert_eq!(signal(&stranger, "name"), Some(0.4));
+
+    let middle_initial = match_record(&record(json!({ "name": "Alice J. Smith" })), &profile, &alice);
+    assert_eq!(signal(&middle_initial, "name"), Some(1.0));
+}
+
+#[test]
+fn listed_age_is_compared_with_birth_year() {
+    let alice = name("Alice", "Smith", None, None);
+    let birth_year = Utc::now().year() - 35;
+    let profile = profile(vec![alice.clone()], Some(birth_year));
+    let age_score = |age: &str| signal(&match_record(&record(json!({ "age": age })), &profile, &alice), "age");
+
+    // Without the birthday, either age is right
+    assert_eq!(age_score("Age 35"), Some(1.0));
+    assert_eq!(age_score("34"), Some(1.0));
+    assert_eq!(age_score("37"), Some(0.6));
+    assert_eq!(age_score("Age 39"), Some(0.2));
+    assert_eq!(age_score("50"), Some(0.0));
+    assert_eq!(age_score("unknown"), None);
+
+    let no_birth_year = MatchProfile { birth_year: None, ..profile.clone() };
+    assert_eq!(signal(&match_record(&record(json!({ "age": "35" })), &no_birth_year, &alice), "age"), None);
+}
+
+#[test]
+fn location_matches_city_then_state() {
+    let alice = name("Alice", "Smith", Some("Portland"), Some("OR"));
+    let profile = profile(vec![alice.clone()], None);
+    let location = |address: &str| {
+        signal(&match_record(&record(json!({ "home_address": address })), &profile, &alice), "location")
+    };
+
+    assert_eq!(location("12 Oak St, Portland, OR 97201"), Some(1.0));
+    assert_eq!(location("9 Elm St, Salem, OR 97301"), Some(0.5));
+    assert_eq!(location("1 Main St, Austin, TX 73301"), Some(0.0));
+}
+
+#[test]
+fn namesake_on_an_email_search_is_not_lifted() {
+    let alice = name("Alice", "Smith", Some("Portland"), Some("OR"));
+    let searched = email("alice@example.com");
+    let profile = profile(vec![alice, searched.clone()], None);
+
+    // Listed under the search, but nothing on the record is the user's
+    let namesake = match_record(
+        &record(json!({ "name": "Alice Smith", "home_address": "1 Main St, Austin, TX 73301" })),
+        &profile,
+        &searched,
+    );
+    assert_eq!(signal(&namesake, "searched"), Some(1.0));
+    assert!(namesake.confidence < 0.95, "{}", namesake.confidence);
+
+    let stranger = match_record(&record(json!({ "name": "Bob Jones" })), &profile, &searched);
+    assert!(stranger.confidence < 0.6, "{}", stranger.confidence);
+}
+
+#[test]
+fn shared_contact_fields_lift_confidence() {
+    let alice = name("Alice", "Smith", None, None);
+    let searched = email("alice@example.com");
+    let profile = profile(vec![alice, searched.clone(), phone("+15551234567")], None);
+
+    let lists_email = match_record(
+        &record(json!({ "name": "A Jones", "email": ["Alice@Example.com"] })),
+        &profile,
+        &searched,
+    );
+    assert_eq!(signal(&lists_email, "email"), Some(1.0));
+    assert_eq!(lists_email.confidence, 0.95);
+
+    // Phone numbers compare by their national digits
+    let lists_phone = match_record(&record(json!({ "phone": ["(555) 123-4567"] })), &profile, &searched);
+    assert_eq!(signal(&lists_phone, "phone"), Some(1.0));
+    assert_eq!(lists_phone.confidence, 0.95);
+
+    // A strong name match keeps its own, higher confidence
+    let exact = match_record(
+        &record(json!({ "name": "Alice Smith", "email": ["alice@example.com"] })),
+        &profile,
+        &searched,
+    );
+    assert_eq!(exact.confidence, 1.0);
+}
+
+#[test]
+fn searched_identifier_counts_even_when_not_in_profile() {
+    let searched = phone("+15551234567");
+    let matched = match_record(&record(json!({ "phone": ["555-123-4567"] })), &MatchProfile::default(), &searched);
+    assert_eq!(matched.confidence, 0.95);
+}
+
+#[test]
+fn listed_address_must_start_with_the_whole_street() {
+    let home = address("1 Main St");
+    let profile = profile(vec![name("Alice", "Smith", None, None), home.clone()], None);
+    let address_score = |listed: &str| {
+        signal(&match_record(&record(json!({ "home_address": listed })), &profile, &home), "address")
+    };
+
+    assert_eq!(address_score("1 Main St, Portland, OR 97201"), Some(1.0));
+    assert_eq!(address_score("1  MAIN st. Portland OR"), Some(1.0));
+    // Neighbours whose house number merely contains the user's
+    assert_eq!(address_score("11 Main St, Portland, OR 97201"), None);
+    assert_eq!(address_score("21 Main Street, Portland, OR"), None);
+    assert_eq!(address_score("Apt 1 Main St"), None);
+
+    let neighbour = record(json!({ "name": "Bob Jones", "home_address": "11 Main St, Portland, OR 97201" }));
+    let matched = match_record(&neighbour, &profile, &home);
+    assert!(matched.confidence < 0.95, "{}", matched.confidence);
+}
@@ -0,0 +1,144 @@
+// tests/removal.rs
+
+// Recognizing a takedown's listing when its broker is searched again.
+
+use chrono::Utc;
+use reqwest::Url;
+use serde_json::{json, Map, Value};
+use shadow_scan_backend::{
+    evidence::Capture,
+    jobs::removal::{is_same_listing, listing_status, ListingStatus},
+    scanner::{
+        broker::{ListedRecord, SearchPage},
+        Identifier,
+    },
+};
+
+fn details(record: Value, listing_url: Option<&str>) -> Value {
+    json!({
+        "broker": "fastpeoplesearch",
+        "searched": { "kind": "email", "value": "alice@example.com" },
+        "record": record,
+        "listing_url": listing_url,
+    })
+}
+
+fn listed(record: Value, listing_url: Option<&str>) -> ListedRecord {
+    ListedRecord {
+        record: record.as_object().cloned().unwrap_or_else(Map::new),
+        listing_url: listing_url.map(|url| Url::parse(url).unwrap()),
+    }
+}
+
+fn page(status: u16, records: Vec<ListedRecord>, shows_no_results: bool) -> SearchPage {
+    SearchPage {
+        searched: Identifier::Email { value: "alice@example.com".to_string() },
+        capture: Capture {
+            url: "https://www.fastpeoplesearch.com/email/alice@example.com".to_string(),
+            status,
+            headers: Vec::new(),
+            content_type: Some("text/html".to_string()),
+            body: b"<html></html>".to_vec().into(),
+            captured_at: Utc::now(),
+        },
+        records,
+        shows_no_results,
+    }
+}
+
+const G123: &str = "https://www.fastpeoplesearch.com/alice-smith_id_G123";
+const G456: &str = "https://www.fastpeoplesearch.com/alice-smith_id_G456";
+
+#[test]
+fn linked_listing_is_recognized_by_its_url_alone() {
+    let tracked = details(json!({ "name": "Alice Smith", "age": "Age 34", "phone": ["(555) 123-4567"] }), Some(G123));
+
+    // Edited, a year older, and a weaker match: still the same listing
+    let edited = listed(json!({ "name": "A Smith", "age": "Age 35", "phone": ["(555) 000-0000"] }), Some(G123));
+    assert!(is_same_listing(&tracked, Some(G123), &edited));
+
+    // Same phone on another page is someone else's listing
+    let household = listed(json!({ "name": "Bob Smith", "phone": ["(555) 123-4567"] }), Some(G456));
+    assert!(!is_same_listing(&tracked, Some(G123), &household));
+}
+
+#[test]
+fn source_link_stands_in_for_a_missing_listing_url() {
+    let tracked = json!({ "broker": "fastpeoplesearch", "record": { "name": "Alice Smith" } });
+    assert!(is_same_listing(&tracked, Some(G123), &listed(json!({ "name": "Someone" }), Some(G123))));
+}
+
+#[test]
+fn unlinked_listing_is_recognized_by_contact_fields() {
+    let tracked = details(
+        json!({ "name": "Alice Smith", "home_address": "12 Oak St", "phone": ["+1 (555) 123-4567"], "age": "34" }),
+        None,
+    );
+
+    let moved = listed(json!({ "name": "Alice Smith", "home_address": "9 Elm St", "phone": ["555-123-4567"] }), None);
+    assert!(is_same_listing(&tracked, None, &moved));
+
+    let renamed = listed(json!({ "name": "Alice Jones", "home_address": " 12  oak st" }), None);
+    assert!(is_same_listing(&tracked, None, &renamed));
+
+    let namesake = listed(json!({ "name": "Alice Smith", "home_address": "1 Main St" }), None);
+    assert!(!is_same_listing(&tracked, None, &namesake));
+}
+
+#[test]
+fn listing_without_contact_fields_is_recognized_by_name() {
+    let tracked = details(json!({ "name": "Alice Smith", "age": "34" }), None);
+    assert!(is_same_listing(&tracked, None, &listed(json!({ "name": "alice  smith", "age": "35" }), None)));
+    assert!(!is_same_listing(&tracked, None, &listed(json!({ "name": "Bob Smith" }), None)));
+    assert!(!is_same_listing(&json!({ "broker": "x", "record": {} }), None, &listed(json!({}), None)));
+}
+
+#[test]
+fn not_found_page_after_a_removal_counts_as_gone() {
+    let tracked = details(json!({ "name": "Alice Smith" }), Some(G123));
+    let pages = [page(404, Vec::new(), false)];
+    assert!(matches!(listing_status(&tracked, Some(G123), &pages), ListingStatus::Gone));
+}
+
+#[test]
+fn no_results_page_after_a_removal_counts_as_gone() {
+    let tracked = details(json!({ "name": "Alice Smith" }), Some(G123));
+    assert!(matches!(
+        listing_status(&tracked, Some(G123), &[page(200, Vec::new(), true)]),
+        ListingStatus::Gone
+    ));
+
+    // Only other people's listings left
+    let others = page(200, vec![listed(json!({ "name": "Bob Jones" }), Some(G456))], false);
+    assert!(matches!(listing_status(&tracked, Some(G123), &[others]), ListingStatus::Gone));
+}
+
+#[test]
+fn listing_still_up_is_found_among_every_record() {
+    let tracked = details(json!({ "name": "Alice Smith" }), Some(G123));
+    let pages = [
+        page(404, Vec::new(), false),
+        page(200, vec![listed(json!({ "name": "Bob Jones" }), Some(G456)), listed(json!({}), Some(G123))], false),
+    ];
+    match listing_status(&tracked, Some(G123), &pages) {
+        ListingStatus::Listed { page, listed } => {
+            assert_eq!(page.capture.status, 200);
+            assert_eq!(listed.listing_url.as_ref().map(Url::as_str), Some(G123));
+        }
+        status => panic!("expected the listing, got {:?}", status),
+    }
+}
+
+#[test]
+fn unreadable_pages_tell_nothing() {
+    let tracked = details(json!({ "name": "Alice Smith" }), Some(G123));
+
+    // A block page or a changed layout: no records, and no word of there being none
+    let blank = [page(200, Vec::new(), false)];
+    assert!(matches!(listing_status(&tracked, Some(G123), &blank), ListingStatus::Unknown(_)));
+
+    let mixed = [page(404, Vec::new(), false), page(200, Vec::new(), false)];
+    assert!(matches!(listing_status(&tracked, Some(G123), &mixed), ListingStatus::Unknown(_)));
+
+    assert!(matches!(listing_status(&tracked, Some(G123), &[]), ListingStatus::Unknown(_)));
+}
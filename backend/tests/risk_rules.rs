@@ -0,0 +1,206 @@
+// tests/risk_rules.rs
+
+// Rating findings with declarative risk rules.
+
+use serde_json::{json, Value};
+use shadow_scan_backend::{
+    models::scan::{FindingType, RiskLevel},
+    risk::{RiskRules, RuleError, DEFAULT_RULES_FILE},
+    scanner::Finding,
+};
+
+fn finding(finding_type: FindingType, details: Value) -> Finding {
+    Finding {
+        finding_type,
+        details,
+        risk_level: RiskLevel::Medium,
+        source_link: None,
+        capture: None,
+    }
+}
+
+fn broker_record(record: Value) -> Finding {
+    finding(FindingType::DataBroker, json!({ "broker": "fastpeoplesearch", "record": record }))
+}
+
+fn rules(toml: &str) -> RiskRules {
+    RiskRules::from_toml(toml).unwrap()
+}
+
+#[test]
+fn all_requires_every_path_present() {
+    let rules = rules(
+        r#"
+        [[rules]]
+        id = "address-and-phone"
+        description = ""
+        risk_level = "critical"
+        all = ["record.home_address", "record.phone"]
+        "#,
+    );
+
+    let both = broker_record(json!({ "home_address": "12 Oak St", "phone": ["(555) 123-4567"] }));
+    assert_eq!(rules.evaluate("fastpeoplesearch", &both), (RiskLevel::Critical, vec!["address-and-phone".to_string()]));
+
+    // Empty values count as absent
+    let empty_phone = broker_record(json!({ "home_address": "12 Oak St", "phone": [] }));
+    assert_eq!(rules.evaluate("fastpeoplesearch", &empty_phone), (RiskLevel::Medium, vec![]));
+    let blank_address = broker_record(json!({ "home_address": "  ", "phone": ["1"] }));
+    assert_eq!(rules.evaluate("fastpeoplesearch", &blank_address).1, Vec::<String>::new());
+}
+
+#[test]
+fn any_requires_one_path_present() {
+    let rules = rules(
+        r#"
+        [[rules]]
+        id = "age-listed"
+        description = ""
+        risk_level = "high"
+        any = ["record.age", "record.dob"]
+        "#,
+    );
+
+    assert_eq!(rules.evaluate("x", &broker_record(json!({ "dob": "1990-01-01" }))).0, RiskLevel::High);
+    assert_eq!(rules.evaluate("x", &broker_record(json!({ "name": "Alice" }))).0, RiskLevel::Medium);
+}
+
+#[test]
+fn none_requires_every_path_absent() {
+    let rules = rules(
+        r#"
+        [[rules]]
+        id = "name-only"
+        description = ""
+        risk_level = "low"
+        all = ["record.name"]
+        none = ["record.home_address", "record.phone"]
+        "#,
+    );
+
+    assert_eq!(rules.evaluate("x", &broker_record(json!({ "name": "Alice" }))).0, RiskLevel::Low);
+    assert_eq!(rules.evaluate("x", &broker_record(json!({ "name": "Alice", "phone": ["1"] }))).0, RiskLevel::Medium);
+}
+
+#[test]
+fn equals_compares_case_insensitively() {
+    let rules = rules(
+        r#"
+        [[rules]]
+        id = "twitter"
+        description = ""
+        risk_level = "high"
+        equals = { platform = "twitter" }
+        "#,
+    );
+
+    let twitter = finding(FindingType::SocialMedia, json!({ "platform": " Twitter ", "username": "alice" }));
+    let other = finding(FindingType::SocialMedia, json!({ "platform": "Mastodon", "username": "alice" }));
+    let missing = finding(FindingType::SocialMedia, json!({ "username": "alice" }));
+    assert_eq!(rules.evaluate("social_media", &twitter).0, RiskLevel::High);
+    assert_eq!(rules.evaluate("social_media", &other).0, RiskLevel::Medium);
+    assert_eq!(rules.evaluate("social_media", &missing).0, RiskLevel::Medium);
+}
+
+#[test]
+fn finding_types_and_sources_restrict_rules() {
+    let rules = rules(
+        r#"
+        [[rules]]
+        id = "breaches"
+        description = ""
+        risk_level = "critical"
+        finding_types = ["email_leak"]
+        sources = ["breach_db"]
+        "#,
+    );
+    let leak = finding(FindingType::EmailLeak, json!({ "leaked_email": "alice@example.com" }));
+
+    assert_eq!(rules.evaluate("breach_db", &leak).0, RiskLevel::Critical);
+    assert_eq!(rules.evaluate("other_db", &leak).0, RiskLevel::Medium);
+    assert_eq!(rules.evaluate("breach_db", &broker_record(json!({ "name": "Alice" }))).0, RiskLevel::Medium);
+}
+
+#[test]
+fn highest_matching_level_wins_and_every_match_is_listed() {
+    let rules = rules(
+        r#"
+        [[rules]]
+        id = "home-address"
+        description = ""
+        risk_level = "high"
+        all = ["record.home_address"]
+
+        [[rules]]
+        id = "listed"
+        description = ""
+        risk_level = "low"
+        finding_types = ["data_broker"]
+        "#,
+    );
+
+    let (level, matched) = rules.evaluate("x", &broker_record(json!({ "home_address": "12 Oak St" })));
+    assert_eq!(level, RiskLevel::High);
+    assert_eq!(matched, vec!["home-address", "listed"]);
+
+    // A matching rule may also lower the scanner's level
+    let (level, matched) = rules.evaluate("x", &broker_record(json!({ "name": "Alice" })));
+    assert_eq!(level, RiskLevel::Low);
+    assert_eq!(matched, vec!["listed"]);
+}
+
+#[test]
+fn scanner_level_is_kept_when_nothing_matches() {
+    let mut leak = finding(FindingType::EmailLeak, json!({}));
+    leak.risk_level = RiskLevel::High;
+    assert_eq!(RiskRules::default().evaluate("breach_db", &leak), (RiskLevel::High, vec![]));
+}
+
+#[test]
+fn duplicate_ids_are_rejected() {
+    let error = RiskRules::from_toml(
+        r#"
+        [[rules]]
+        id = "same"
+        description = ""
+        risk_level = "low"
+        finding_types = ["social_media"]
+
+        [[rules]]
+        id = "same"
+        description = ""
+        risk_level = "high"
+        finding_types = ["email_leak"]
+        "#,
+    )
+    .unwrap_err();
+    assert!(matches!(error, RuleError::Invalid(id, _) if id == "same"));
+}
+
+#[test]
+fn rules_without_conditions_are_rejected() {
+    let error = RiskRules::from_toml(
+        r#"
+        [[rules]]
+        id = "everything"
+        description = ""
+        risk_level = "critical"
+        "#,
+    )
+    .unwrap_err();
+    assert!(matches!(error, RuleError::Invalid(id, _) if id == "everything"));
+}
+
+#[test]
+fn default_username_rule_only_matches_bare_usernames() {
+    let rules = RiskRules::load_file(DEFAULT_RULES_FILE).unwrap();
+
+    let bare = finding(FindingType::SocialMedia, json!({ "platform": "Twitter", "username": "alice" }));
+    assert_eq!(rules.evaluate("social_media", &bare).1, vec!["username-only"]);
+
+    let detailed = finding(
+        FindingType::SocialMedia,
+        json!({ "platform": "Twitter", "username": "alice", "location": "Portland, OR" }),
+    );
+    assert!(rules.evaluate("social_media", &detailed).1.is_empty());
+}
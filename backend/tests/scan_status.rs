@@ -0,0 +1,39 @@
+// tests/scan_status.rs
+
+// The scan status state machine: every pair of statuses, allowed or not.
+
+use shadow_scan_backend::models::scan::ScanStatus::{self, *};
+
+#[test]
+fn transitions() {
+    let table: [(ScanStatus, [bool; 5]); 5] = [
+        //              Pending InProgress Completed Failed Cancelled
+        (Pending,    [false, true,  false, true,  true]),
+        (InProgress, [false, true,  true,  true,  true]),
+        (Completed,  [false, false, false, false, false]),
+        (Failed,     [false, false, false, false, false]),
+        (Cancelled,  [false, false, false, false, false]),
+    ];
+
+    for (from, allowed) in table {
+        for (next, allowed) in ScanStatus::ALL.into_iter().zip(allowed) {
+            assert_eq!(from.can_transition_to(next), allowed, "{} -> {}", from, next);
+        }
+    }
+}
+
+#[test]
+fn terminal_statuses_have_no_way_out() {
+    for status in ScanStatus::ALL {
+        let stuck = ScanStatus::ALL.iter().all(|next| !status.can_transition_to(*next));
+        assert_eq!(status.is_terminal(), stuck, "{}", status);
+    }
+}
+
+#[test]
+fn allowed_predecessors() {
+    assert_eq!(Pending.allowed_predecessors(), vec![]);
+    assert_eq!(InProgress.allowed_predecessors(), vec![Pending, InProgress]);
+    assert_eq!(Completed.allowed_predecessors(), vec![InProgress]);
+    assert_eq!(Cancelled.allowed_predecessors(), vec![Pending, InProgress]);
+}
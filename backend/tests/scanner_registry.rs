@@ -0,0 +1,106 @@
+// tests/scanner_registry.rs
+
+// Exercises the `Scanner` trait through the registry and the built-in sources.
+
+use async_trait::async_trait;
+use shadow_scan_backend::{
+    models::scan::{FindingType, RiskLevel},
+    scanner::{
+        breach_db::BreachDbScanner, social_media::SocialMediaScanner, Finding, Identifier, IdentifierKind,
+        ScanTarget, Scanner, ScannerError, ScannerRegistry,
+    },
+};
+use tokio_util::sync::CancellationToken;
+
+/// Reports one finding per phone number it is given.
+struct PhoneScanner;
+
+#[async_trait]
+impl Scanner for PhoneScanner {
+    fn name(&self) -> &str {
+        "phone_book"
+    }
+
+    fn supported_kinds(&self) -> &[IdentifierKind] {
+        &[IdentifierKind::Phone]
+    }
+
+    async fn scan(&self, target: &ScanTarget, _cancel: &CancellationToken) -> Result<Vec<Finding>, ScannerError> {
+        Ok(target
+            .identifiers
+            .iter()
+            .filter_map(|identifier| match identifier {
+                Identifier::Phone { value } => Some(value),
+                _ => None,
+            })
+            .map(|phone| Finding {
+                finding_type: FindingType::DataBroker,
+                details: serde_json::json!({ "phone": phone }),
+                risk_level: RiskLevel::Medium,
+                source_link: None,
+                capture: None,
+            })
+            .collect())
+    }
+}
+
+fn target() -> ScanTarget {
+    ScanTarget {
+        identifiers: vec![
+            Identifier::Email { value: "alice@example.com".to_string() },
+            Identifier::Phone { value: "+15551234567".to_string() },
+            Identifier::Username { value: "alice".to_string() },
+        ],
+        ..Default::default()
+    }
+}
+
+fn names(registry: &ScannerRegistry) -> Vec<&str> {
+    registry.scanners().iter().map(|scanner| scanner.name()).collect()
+}
+
+#[test]
+fn defaults_register_builtin_sources() {
+    assert_eq!(names(&ScannerRegistry::with_defaults()), vec!["breach_db", "social_media"]);
+    assert!(ScannerRegistry::new().scanners().is_empty());
+}
+
+#[tokio::test]
+async fn registered_scanner_is_dispatched_through_trait_object() {
+    let mut registry = ScannerRegistry::with_defaults();
+    registry.register(PhoneScanner);
+    assert_eq!(names(&registry), vec!["breach_db", "social_media", "phone_book"]);
+
+    let scanner = registry.scanners().last().unwrap().clone();
+    let source_target = target().restricted_to(scanner.supported_kinds());
+    let findings = scanner.scan(&source_target, &CancellationToken::new()).await.unwrap();
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].details["phone"], "+15551234567");
+}
+
+#[test]
+fn restricted_to_keeps_only_supported_kinds() {
+    let restricted = target().restricted_to(&[IdentifierKind::Email, IdentifierKind::Username]);
+    let kinds: Vec<_> = restricted.identifiers.iter().map(Identifier::kind).collect();
+    assert_eq!(kinds, vec![IdentifierKind::Email, IdentifierKind::Username]);
+
+    assert!(target().restricted_to(&[IdentifierKind::Address]).is_empty());
+}
+
+#[tokio::test]
+async fn breach_db_reports_each_email() {
+    let findings = BreachDbScanner.scan(&target(), &CancellationToken::new()).await.unwrap();
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].finding_type, FindingType::EmailLeak);
+    assert_eq!(findings[0].details["leaked_email"], "alice@example.com");
+}
+
+#[tokio::test]
+async fn social_media_deduplicates_username_and_email_local_part() {
+    let findings = SocialMediaScanner.scan(&target(), &CancellationToken::new()).await.unwrap();
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].details["username"], "alice");
+}
@@ -0,0 +1,109 @@
+// tests/schedule.rs
+
+// When scheduled scans run, and which cron expressions may be scheduled.
+
+use chrono::{DateTime, Datelike, TimeZone, Utc, Weekday};
+use shadow_scan_backend::models::schedule::{next_run_after, parse_cron, validate_cron, ScheduleFrequency};
+
+fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+}
+
+#[test]
+fn first_run_is_the_start_when_it_lies_ahead() {
+    let starts_at = at(2025, 3, 10, 8, 0);
+    let next = next_run_after(ScheduleFrequency::Weekly, None, starts_at, at(2025, 3, 1, 0, 0)).unwrap();
+    assert_eq!(next, starts_at);
+}
+
+#[test]
+fn daily_runs_stay_on_the_start_time() {
+    let starts_at = at(2025, 3, 10, 8, 0);
+
+    // Strictly after: a run due right now is the next day's
+    assert_eq!(
+        next_run_after(ScheduleFrequency::Daily, None, starts_at, starts_at).unwrap(),
+        at(2025, 3, 11, 8, 0)
+    );
+    // Runs missed while nothing ran are skipped, not caught up
+    assert_eq!(
+        next_run_after(ScheduleFrequency::Daily, None, starts_at, at(2025, 3, 20, 9, 30)).unwrap(),
+        at(2025, 3, 21, 8, 0)
+    );
+}
+
+#[test]
+fn weekly_runs_count_whole_weeks() {
+    let starts_at = at(2025, 3, 10, 8, 0);
+    assert_eq!(
+        next_run_after(ScheduleFrequency::Weekly, None, starts_at, at(2025, 3, 18, 0, 0)).unwrap(),
+        at(2025, 3, 24, 8, 0)
+    );
+}
+
+#[test]
+fn monthly_runs_come_back_to_the_anchor_day() {
+    let starts_at = at(2025, 1, 31, 8, 0);
+
+    assert_eq!(
+        next_run_after(ScheduleFrequency::Monthly, None, starts_at, starts_at).unwrap(),
+        at(2025, 2, 28, 8, 0)
+    );
+    assert_eq!(
+        next_run_after(ScheduleFrequency::Monthly, None, starts_at, at(2025, 2, 28, 8, 0)).unwrap(),
+        at(2025, 3, 31, 8, 0)
+    );
+}
+
+#[test]
+fn cron_runs_follow_the_expression() {
+    let starts_at = at(2025, 3, 10, 0, 0);
+
+    // Five-field form, Mondays at 09:00
+    assert_eq!(
+        next_run_after(ScheduleFrequency::Cron, Some("0 9 * * Mon"), starts_at, at(2025, 3, 10, 10, 0)).unwrap(),
+        at(2025, 3, 17, 9, 0)
+    );
+    // Never before the start, even when asked about an earlier time
+    assert_eq!(
+        next_run_after(ScheduleFrequency::Cron, Some("0 9 * * *"), starts_at, at(2025, 3, 1, 0, 0)).unwrap(),
+        at(2025, 3, 10, 9, 0)
+    );
+    assert!(next_run_after(ScheduleFrequency::Cron, Some("not cron"), starts_at, starts_at).is_err());
+}
+
+#[test]
+fn cron_expressions_running_more_than_daily_are_rejected() {
+    for expression in ["* * * * * *", "*/5 * * * *", "0 * * * *", "0 0 0,1 * * *", "0 0,12 * * *"] {
+        assert!(validate_cron(expression).is_err(), "{} was accepted", expression);
+    }
+    for expression in ["0 9 * * *", "0 9 * * 1-5", "30 2 1 * *"] {
+        assert!(validate_cron(expression).is_ok(), "{} was rejected", expression);
+    }
+    assert!(validate_cron("61 * * * *").is_err());
+}
+
+#[test]
+fn cron_weekdays_are_numbered_as_in_crontab() {
+    // 2025-03-09 is a Sunday
+    let sunday = at(2025, 3, 9, 0, 0);
+    let next = next_run_after(ScheduleFrequency::Cron, Some("0 6 * * 1"), sunday, sunday).unwrap();
+    assert_eq!(next.weekday(), Weekday::Mon);
+    assert_eq!(next, at(2025, 3, 10, 6, 0));
+
+    let weekdays = |expression: &str| -> Vec<Weekday> {
+        parse_cron(expression).unwrap().after(&sunday).take(7).map(|run| run.weekday()).collect()
+    };
+    use Weekday::*;
+    assert_eq!(weekdays("0 6 * * 1-5"), vec![Mon, Tue, Wed, Thu, Fri, Mon, Tue]);
+    assert_eq!(weekdays("0 6 * * 0"), weekdays("0 6 * * 7"));
+    assert_eq!(weekdays("0 6 * * 0")[0], Sun);
+    assert_eq!(weekdays("0 6 * * 5-7"), vec![Sun, Fri, Sat, Sun, Fri, Sat, Sun]);
+    assert_eq!(weekdays("0 6 * * 1,3"), vec![Mon, Wed, Mon, Wed, Mon, Wed, Mon]);
+    assert_eq!(weekdays("0 6 * * 1-5/2"), vec![Mon, Wed, Fri, Mon, Wed, Fri, Mon]);
+    assert_eq!(weekdays("0 6 * * */2"), vec![Sun, Tue, Thu, Sat, Sun, Tue, Thu]);
+    assert_eq!(weekdays("0 6 * * Mon-Fri"), weekdays("0 6 * * 1-5"));
+
+    // The crate's own six-field form keeps its numbering, 1 being Sunday
+    assert_eq!(weekdays("0 0 6 * * 1")[0], Sun);
+}
@@ -0,0 +1,176 @@
+// tests/smtp_mailer.rs
+
+// Delivers mail through `SmtpMailer` to a minimal SMTP catcher running in-process.
+
+use shadow_scan_backend::mail::{
+    mbox::MboxMailer,
+    smtp::{SmtpConfig, SmtpMailer, SmtpSecurity},
+    EmailMessage, Mailer,
+};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpListener,
+};
+
+#[derive(Debug, Default, Clone)]
+struct Received {
+    mail_from: String,
+    rcpt_to: Vec<String>,
+    data: String,
+}
+
+/// Accepts connections on a local port and records every message it is sent.
+/// Recipients listed in `reject` are refused with a 550.
+async fn start_catcher(reject: &'static [&'static str]) -> (u16, Arc<Mutex<Vec<Received>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let inbox = received.clone();
+    tokio::spawn(async move {
+        loop {
+            let Ok((socket, _)) = listener.accept().await else { return };
+            let inbox = inbox.clone();
+            tokio::spawn(async move {
+                let (read, mut write) = socket.into_split();
+                let mut lines = BufReader::new(read).lines();
+                let mut current = Received::default();
+                write.write_all(b"220 catcher ESMTP\r\n").await.unwrap();
+
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let command = line.to_ascii_uppercase();
+                    let reply: &[u8] = if command.starts_with("EHLO") || command.starts_with("HELO") {
+                        b"250-catcher\r\n250 8BITMIME\r\n"
+                    } else if command.starts_with("MAIL FROM:") {
+                        current = Received { mail_from: line[10..].trim().to_string(), ..Default::default() };
+                        b"250 OK\r\n"
+                    } else if command.starts_with("RCPT TO:") {
+                        let rcpt = line[8..].trim().trim_matches(|c| c == '<' || c == '>').to_string();
+                        if reject.contains(&rcpt.as_str()) {
+                            b"550 No such user\r\n"
+                        } else {
+                            current.rcpt_to.push(rcpt);
+                            b"250 OK\r\n"
+                        }
+                    } else if command == "DATA" {
+                        write.write_all(b"354 End data with <CR><LF>.<CR><LF>\r\n").await.unwrap();
+                        while let Ok(Some(line)) = lines.next_line().await {
+                            if line == "." {
+                                break;
+                            }
+                            current.data.push_str(line.strip_prefix('.').unwrap_or(&line));
+                            current.data.push('\n');
+                        }
+                        inbox.lock().unwrap().push(std::mem::take(&mut current));
+                        b"250 Queued\r\n"
+                    } else if command == "RSET" || command == "NOOP" {
+                        b"250 OK\r\n"
+                    } else if command == "QUIT" {
+                        let _ = write.write_all(b"221 Bye\r\n").await;
+                        return;
+                    } else {
+                        b"502 Command not implemented\r\n"
+                    };
+                    if write.write_all(reply).await.is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+
+    (port, received)
+}
+
+fn mailer(port: u16) -> SmtpMailer {
+    let config = SmtpConfig {
+        host: "127.0.0.1".to_string(),
+        port,
+        security: SmtpSecurity::None,
+        username: None,
+        password: None,
+        timeout: Duration::from_secs(5),
+    };
+    SmtpMailer::new(&config, "ShadowScan <no-reply@test.local>".parse().unwrap()).unwrap()
+}
+
+fn message(to: &str) -> EmailMessage {
+    EmailMessage {
+        to: to.to_string(),
+        subject: "Your verification code".to_string(),
+        text_body: "Your code is 123456.\n.leading dot\n".to_string(),
+        html_body: Some("<p>Your code is <b>123456</b>.</p>".to_string()),
+        reply_to: Some("alice@example.com".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn delivers_multipart_message() {
+    let (port, received) = start_catcher(&[]).await;
+
+    mailer(port).send(&message("bob@example.com")).await.unwrap();
+
+    let received = received.lock().unwrap().clone();
+    assert_eq!(received.len(), 1);
+    let mail = &received[0];
+    assert_eq!(mail.mail_from, "<no-reply@test.local>");
+    assert_eq!(mail.rcpt_to, vec!["bob@example.com"]);
+    assert!(mail.data.contains("Subject: Your verification code"));
+    assert!(mail.data.contains("To: bob@example.com"));
+    assert!(mail.data.contains("Reply-To: alice@example.com"));
+    assert!(mail.data.contains("multipart/alternative"));
+    assert!(mail.data.contains("Your code is 123456."));
+    assert!(mail.data.contains("\n.leading dot\n"));
+    assert!(mail.data.contains("<b>123456</b>"));
+}
+
+#[tokio::test]
+async fn reports_rejected_recipient() {
+    let (port, received) = start_catcher(&["nobody@example.com"]).await;
+
+    let error = mailer(port).send(&message("nobody@example.com")).await.unwrap_err();
+
+    assert!(error.message.contains("nobody@example.com"), "{}", error);
+    assert!(error.permanent, "a 550 is not worth retrying");
+    assert!(received.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn reports_unreachable_server() {
+    // Bind and drop a listener to find a port nothing is listening on
+    let port = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap().port();
+
+    let error = mailer(port).send(&message("bob@example.com")).await.unwrap_err();
+    assert!(!error.permanent, "the server may come back");
+}
+
+#[tokio::test]
+async fn invalid_address_fails_permanently() {
+    let (port, _) = start_catcher(&[]).await;
+
+    let error = mailer(port).send(&message("not an address")).await.unwrap_err();
+
+    assert!(error.permanent, "{}", error);
+}
+
+#[tokio::test]
+async fn mbox_quotes_from_lines() {
+    let path = std::env::temp_dir().join(format!("shadowscan-{}.mbox", uuid::Uuid::new_v4()));
+    let mailer = MboxMailer::new(&path, "ShadowScan <no-reply@test.local>".parse().unwrap());
+    let mut first = message("bob@example.com");
+    first.html_body = None;
+    first.text_body = "From here on, nothing changes.\n".to_string();
+
+    mailer.send(&first).await.unwrap();
+    mailer.send(&message("carol@example.com")).await.unwrap();
+
+    let mbox = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(mbox.lines().filter(|line| line.starts_with("From no-reply@test.local ")).count(), 2);
+    assert!(mbox.contains("\n>From here on, nothing changes.\n"));
+    assert!(mbox.contains("To: carol@example.com"));
+}